@@ -1,5 +1,6 @@
 use bdd::closet::Closet;
 use bdd::node::Node;
+use bdd::node::operation;
 use core::Family;
 use core::Item;
 use core::Outfit;
@@ -11,6 +12,8 @@ use std::collections::BTreeMap;
 
 impl Closet {
     pub fn complete_outfit(&self, selections: Vec<Item>) -> Result<Outfit, OutfitError> {
+        let _operation = operation();
+
         validate(self, &selections)?;
 
         let mut root: Node = selections.iter()
@@ -0,0 +1,188 @@
+use bdd::closet::Closet;
+use bdd::node::Node;
+use bdd::node::NodeId;
+use bdd::node::operation;
+use core::Item;
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+impl Closet {
+    /// The diagram behind this closet in Graphviz DOT, with each node written once
+    ///
+    /// Low edges are dashed and high edges are solid
+    pub fn to_dot(&self) -> String {
+        to_dot(self, None)
+    }
+
+    /// Like `to_dot`, with the edges that checking `outfit` follows drawn in red
+    pub fn to_dot_highlighting(&self, outfit: &[Item]) -> String {
+        to_dot(self, Some(outfit))
+    }
+}
+
+fn to_dot(closet: &Closet, highlight: Option<&[Item]>) -> String {
+    let _operation = operation();
+
+    let root = NodeId::from(closet.root());
+    let path = highlight
+        .map(|outfit| path(root, outfit))
+        .unwrap_or_default();
+
+    let mut lines = vec![];
+    visit(root, &path, &mut HashMap::new(), &mut lines);
+
+    let body: String = lines.iter()
+        .map(|line| format!("    {}\n", line))
+        .collect();
+    format!("digraph {{\n{}}}", body)
+}
+
+// Path, edge and escape copy weave::dot on purpose: bowtie-core does not depend on weave, so keep the two in sync
+
+/// Path remembers the nodes a walk down the diagram visits and which edge it takes out of each
+#[derive(Default)]
+struct Path {
+    nodes: HashSet<NodeId>,
+    edges: HashSet<(NodeId, bool)>,
+}
+
+/// Follows the high edge of every item in `outfit` and the low edge of every other item
+fn path(root: NodeId, outfit: &[Item]) -> Path {
+    let mut path = Path::default();
+    let mut id = root;
+    while let Node::Branch(item, low, high) = Node::from(id) {
+        let take_high = outfit.contains(&item);
+        path.nodes.insert(id);
+        path.edges.insert((id, take_high));
+        id = if take_high { high } else { low };
+    }
+    path.nodes.insert(id);
+
+    path
+}
+
+fn visit(id: NodeId, path: &Path, names: &mut HashMap<NodeId, usize>, lines: &mut Vec<String>) -> usize {
+    if let Some(name) = names.get(&id) {
+        return *name;
+    }
+
+    let name = names.len();
+    names.insert(id, name);
+
+    let color = if path.nodes.contains(&id) { ", color=red" } else { "" };
+    match Node::from(id) {
+        Node::Branch(item, low, high) => {
+            lines.push(format!("{} [label=\"{}\"{}];", name, escape(&String::from(item)), color));
+
+            let low_name = visit(low, path, names, lines);
+            let high_name = visit(high, path, names, lines);
+            lines.push(edge(name, low_name, false, path.edges.contains(&(id, false))));
+            lines.push(edge(name, high_name, true, path.edges.contains(&(id, true))));
+        }
+        Node::Leaf(value) => lines.push(format!("{} [label=\"{}\", shape=box{}];", name, value, color)),
+    }
+
+    name
+}
+
+fn edge(from: usize, to: usize, high: bool, highlighted: bool) -> String {
+    let attributes = match (high, highlighted) {
+        (false, false) => " [style=dashed]",
+        (false, true) => " [style=dashed, color=red, penwidth=2]",
+        (true, false) => "",
+        (true, true) => " [color=red, penwidth=2]",
+    };
+
+    format!("{} -> {}{};", from, to, attributes)
+}
+
+fn escape(label: &str) -> String {
+    label.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use bdd::closet_builder::ClosetBuilder;
+    use core::Family;
+    use core::Item;
+
+    #[test]
+    fn to_dot_labels_branches_with_items() {
+        let shirt1 = Item::new("shirts:1");
+        let shirt2 = Item::new("shirts:2");
+        let shirts = Family::new("shirts");
+
+        let closet = ClosetBuilder::new()
+            .add_item(&shirts, &shirt1)
+            .add_item(&shirts, &shirt2)
+            .must_build();
+
+        assert_eq!(
+            "digraph {
+    0 [label=\"shirts:1\"];
+    1 [label=\"shirts:2\"];
+    2 [label=\"false\", shape=box];
+    3 [label=\"true\", shape=box];
+    1 -> 2 [style=dashed];
+    1 -> 3;
+    4 [label=\"shirts:2\"];
+    4 -> 3 [style=dashed];
+    4 -> 2;
+    0 -> 1 [style=dashed];
+    0 -> 4;
+}",
+            closet.to_dot()
+        );
+    }
+
+    #[test]
+    fn to_dot_highlighting_marks_the_path_of_an_outfit() {
+        let shirt1 = Item::new("shirts:1");
+        let shirt2 = Item::new("shirts:2");
+        let shirts = Family::new("shirts");
+
+        let closet = ClosetBuilder::new()
+            .add_item(&shirts, &shirt1)
+            .add_item(&shirts, &shirt2)
+            .must_build();
+
+        assert_eq!(
+            "digraph {
+    0 [label=\"shirts:1\", color=red];
+    1 [label=\"shirts:2\", color=red];
+    2 [label=\"false\", shape=box];
+    3 [label=\"true\", shape=box, color=red];
+    1 -> 2 [style=dashed];
+    1 -> 3 [color=red, penwidth=2];
+    4 [label=\"shirts:2\"];
+    4 -> 3 [style=dashed];
+    4 -> 2;
+    0 -> 1 [style=dashed, color=red, penwidth=2];
+    0 -> 4;
+}",
+            closet.to_dot_highlighting(&[shirt2])
+        );
+    }
+
+    #[test]
+    fn to_dot_writes_shared_nodes_once() {
+        let shirts = Family::new("shirts");
+        let pants = Family::new("pants");
+
+        let closet = (1..9)
+            .fold(ClosetBuilder::new(), |builder, i| {
+                builder
+                    .add_item(&shirts, &Item::new(format!("shirts:{}", i)))
+                    .add_item(&pants, &Item::new(format!("pants:{}", i)))
+            })
+            .must_build();
+
+        let dot = closet.to_dot();
+        let nodes = dot.matches("label=").count() as u64;
+
+        // Each family of 8 needs 8 nodes before its item is chosen and 7 after, plus the two leaves
+        assert_eq!(2 * (8 + 7) + 2, nodes);
+        assert!(nodes < closet.node_count());
+        assert_eq!(2 * (nodes - 2), dot.matches("->").count() as u64);
+    }
+}
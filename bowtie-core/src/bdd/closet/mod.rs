@@ -1,4 +1,5 @@
 use bdd::node::Node;
+use bdd::node::Root;
 use core::Family;
 use core::Item;
 use core::ItemStatus;
@@ -6,6 +7,7 @@ use std::collections::BTreeMap;
 
 mod categorize;
 mod complete_outfit;
+mod dot;
 mod node_count;
 mod select;
 
@@ -13,7 +15,7 @@ mod select;
 pub struct Closet {
     item_index: BTreeMap<Item, Family>,
     summary: Vec<ItemStatus>,
-    root: Node,
+    root: Root,
 }
 
 impl Closet {
@@ -24,12 +26,12 @@ impl Closet {
         Closet {
             item_index,
             summary: Node::summarize(&root),
-            root,
+            root: Root::new(root),
         }
     }
 
     pub fn root(&self) -> &Node {
-        &self.root
+        &*self.root
     }
 
     pub fn item_index(&self) -> &BTreeMap<Item, Family> {
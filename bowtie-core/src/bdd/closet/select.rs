@@ -2,6 +2,8 @@ use itertools::Itertools;
 
 use bdd::closet::Closet;
 use bdd::node::Node;
+use bdd::node::operation;
+use bdd::node::Root;
 use core::Item;
 use core::ItemStatus;
 use core::SelectItemError;
@@ -11,6 +13,8 @@ impl Closet {
         validate_selection_is_known(&self, item)?;
         validate_selection_not_excluded(&self.summary, item)?;
 
+        let _operation = operation();
+
         let item_index = self.item_index.clone();
         let root = Node::restrict(&self.root, item, true);
         let summary = Node::summarize(&root);
@@ -24,7 +28,7 @@ impl Closet {
             .sorted()
             .collect_vec();
 
-        Ok(Closet { item_index, summary, root })
+        Ok(Closet { item_index, summary, root: Root::new(root) })
     }
 }
 
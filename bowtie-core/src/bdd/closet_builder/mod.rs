@@ -1,5 +1,6 @@
 use bdd::closet::Closet;
 use bdd::node::Node;
+use bdd::node::operation;
 use closet_builder::ClosetBuilderError;
 use closet_builder::validate_closet;
 use core::Family;
@@ -73,6 +74,8 @@ impl ClosetBuilder {
     pub fn build(&self) -> Result<Closet, ClosetBuilderError> {
         validate_closet(&self.contents, &self.item_index, &self.exclusions, &self.inclusions)?;
 
+        let _operation = operation();
+
         let root = self.contents.iter()
             .map(|(_, items)| ClosetBuilder::sibling_relationship(items))
             .fold(Node::TRUE_LEAF, |other, family_node| other & family_node);
@@ -1,8 +1,10 @@
 use bdd::node::Node;
 use bdd::node::NodeId;
+use std::cell::Cell;
 use std::collections::hash_map::Entry::*;
 use std::collections::HashMap;
 use std::sync::RwLock;
+use std::sync::RwLockReadGuard;
 
 lazy_static! {
     pub static ref ARENA: RwLock<Arena> = {
@@ -11,12 +13,27 @@ lazy_static! {
         a.add(Node::TRUE_LEAF);
         RwLock::new(a)
     };
+
+    static ref OPERATIONS: RwLock<()> = RwLock::new(());
+}
+
+thread_local! {
+    static OPERATION_DEPTH: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Collection reports what a garbage collection pass over the arena reclaimed
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub struct Collection {
+    pub reclaimed: usize,
+    pub live: usize,
 }
 
 #[derive(Default)]
 pub struct Arena {
-    nodes: Vec<Node>,
+    nodes: Vec<Option<Node>>,
     node_index: HashMap<Node, NodeId>,
+    free: Vec<NodeId>,
+    roots: HashMap<NodeId, usize>,
 }
 
 impl Arena {
@@ -28,21 +45,77 @@ impl Arena {
         match self.node_index.entry(node.clone()) {
             Occupied(entry) => *entry.get(),
             Vacant(entry) => {
-                let index = NodeId(self.nodes.len());
+                let index = match self.free.pop() {
+                    Some(index) => {
+                        self.nodes[index.0] = Some(node);
+                        index
+                    }
+                    None => {
+                        self.nodes.push(Some(node));
+                        NodeId(self.nodes.len() - 1)
+                    }
+                };
 
-                self.nodes.push(node);
                 *entry.insert(index)
             }
         }
     }
 
     pub fn get(&self, index: NodeId) -> Option<&Node> {
-        self.nodes.get(index.0)
+        self.nodes.get(index.0).and_then(Option::as_ref)
     }
 
     #[allow(dead_code)]
     pub fn count(&self) -> usize {
-        self.nodes.len()
+        self.node_index.len()
+    }
+
+    /// Keeps `root`, and every node below it, alive until a matching `release`
+    pub fn retain(&mut self, root: NodeId) {
+        *self.roots.entry(root).or_insert(0) += 1;
+    }
+
+    pub fn release(&mut self, root: NodeId) {
+        if let Occupied(mut entry) = self.roots.entry(root) {
+            *entry.get_mut() -= 1;
+            if *entry.get() == 0 {
+                entry.remove();
+            }
+        }
+    }
+
+    /// Frees every branch that is not reachable from a retained root
+    pub fn collect(&mut self) -> Collection {
+        let mut marked = vec![false; self.nodes.len()];
+
+        let mut queue: Vec<NodeId> = self.roots.keys().cloned().collect();
+        while let Some(id) = queue.pop() {
+            if marked[id.0] {
+                continue;
+            }
+
+            marked[id.0] = true;
+            if let Some(Node::Branch(_, low, high)) = self.nodes[id.0] {
+                queue.push(low);
+                queue.push(high);
+            }
+        }
+
+        let mut reclaimed = 0;
+        for (index, marked) in marked.into_iter().enumerate() {
+            if marked {
+                continue;
+            }
+
+            if let Some(Node::Branch(..)) = self.nodes[index] {
+                let node = self.nodes[index].take().unwrap();
+                self.node_index.remove(&node);
+                self.free.push(NodeId(index));
+                reclaimed += 1;
+            }
+        }
+
+        Collection { reclaimed, live: self.count() }
     }
 }
 
@@ -65,10 +138,47 @@ pub fn count() -> usize {
     arena.count()
 }
 
+/// OperationGuard holds off `collect` while an operation works with nodes that are not yet rooted
+pub struct OperationGuard {
+    _operations: Option<RwLockReadGuard<'static, ()>>,
+}
+
+impl Drop for OperationGuard {
+    fn drop(&mut self) {
+        OPERATION_DEPTH.with(|depth| depth.set(depth.get() - 1));
+    }
+}
+
+/// Marks the current thread as working with nodes until the returned guard is dropped
+///
+/// Guards nest, so only the outermost one on a thread waits for a running collection
+pub fn operation() -> OperationGuard {
+    let operations = OPERATION_DEPTH.with(|depth| {
+        let outermost = depth.get() == 0;
+        depth.set(depth.get() + 1);
+
+        if outermost { Some(OPERATIONS.read().unwrap()) } else { None }
+    });
+
+    OperationGuard { _operations: operations }
+}
+
+/// Frees every node that is not reachable from a live `Closet`
+///
+/// Waits for running operations to finish, so it must not be called from inside one
+pub fn collect() -> Collection {
+    let _operations = OPERATIONS.write().unwrap();
+
+    ARENA.write().unwrap().collect()
+}
+
 #[cfg(test)]
 mod tests {
     use bdd::node::arena::Arena;
+    use bdd::node::arena::Collection;
     use bdd::node::Node;
+    use bdd::node::NodeId;
+    use core::Item;
 
     #[test]
     fn add() {
@@ -95,4 +205,39 @@ mod tests {
 
         assert_eq!(node1_id, node2_id);
     }
+
+    #[test]
+    fn collect_frees_unreachable_nodes() {
+        let mut arena = Arena::new();
+        let false_id = arena.add(Node::FALSE_LEAF);
+        let true_id = arena.add(Node::TRUE_LEAF);
+
+        let leaf_id = arena.add(Node::Branch(Item::new("2"), false_id, true_id));
+        let root_id = arena.add(Node::Branch(Item::new("1"), false_id, leaf_id));
+        let garbage_id = arena.add(Node::Branch(Item::new("3"), false_id, true_id));
+
+        arena.retain(root_id);
+        let collection = arena.collect();
+
+        assert_eq!(Collection { reclaimed: 1, live: 4 }, collection);
+        assert!(arena.get(root_id).is_some());
+        assert!(arena.get(leaf_id).is_some());
+        assert!(arena.get(garbage_id).is_none());
+    }
+
+    #[test]
+    fn add_reuses_freed_ids() {
+        let mut arena = Arena::new();
+        let false_id = arena.add(Node::FALSE_LEAF);
+        let true_id = arena.add(Node::TRUE_LEAF);
+
+        let garbage_id = arena.add(Node::Branch(Item::new("1"), false_id, true_id));
+        arena.collect();
+
+        let node = Node::Branch(Item::new("2"), false_id, true_id);
+        let node_id: NodeId = arena.add(node.clone());
+
+        assert_eq!(garbage_id, node_id);
+        assert_eq!(Some(&node), arena.get(node_id));
+    }
 }
@@ -1,3 +1,7 @@
+pub use self::arena::collect;
+pub use self::arena::Collection;
+pub(crate) use self::arena::operation;
+pub(crate) use self::root::Root;
 use core::Item;
 use std::fmt;
 
@@ -6,6 +10,7 @@ mod bit_operations;
 mod hash_structure;
 mod reduce;
 mod restrict;
+mod root;
 mod serialize;
 mod summarize;
 mod structure;
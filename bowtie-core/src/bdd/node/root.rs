@@ -0,0 +1,66 @@
+use bdd::node::arena;
+use bdd::node::arena::ARENA;
+use bdd::node::Node;
+use bdd::node::NodeId;
+use serde::de::{Deserialize, Deserializer};
+use serde::ser::{Serialize, Serializer};
+use std::fmt;
+use std::ops::Deref;
+
+/// Root retains a node in the arena, keeping it and everything below it alive across collections
+#[derive(PartialEq)]
+pub struct Root {
+    id: NodeId,
+    node: Node,
+}
+
+impl Root {
+    pub fn new(node: Node) -> Root {
+        let id = NodeId::from(&node);
+        ARENA.write().unwrap().retain(id);
+
+        Root { id, node }
+    }
+}
+
+impl Deref for Root {
+    type Target = Node;
+
+    fn deref(&self) -> &Node {
+        &self.node
+    }
+}
+
+impl Clone for Root {
+    fn clone(&self) -> Self {
+        Root::new(self.node.clone())
+    }
+}
+
+impl Drop for Root {
+    fn drop(&mut self) {
+        if let Ok(mut arena) = ARENA.write() {
+            arena.release(self.id);
+        }
+    }
+}
+
+impl fmt::Debug for Root {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self.node)
+    }
+}
+
+impl Serialize for Root {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.node.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Root {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let _operation = arena::operation();
+
+        Node::deserialize(deserializer).map(Root::new)
+    }
+}
@@ -0,0 +1,89 @@
+extern crate bowtie_core;
+
+#[cfg(test)]
+mod collect_tests {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::thread;
+
+    use bowtie_core::bdd::collect;
+    use bowtie_core::bdd::ClosetBuilder;
+    use bowtie_core::core::Family;
+    use bowtie_core::core::Item;
+    use bowtie_core::core::Outfit;
+    use bowtie_core::core::OutfitError::IncompatibleSelections;
+
+    #[test]
+    fn collect_reclaims_dropped_closets_and_keeps_live_ones() {
+        let blue = Item::new("shirts:blue");
+        let red = Item::new("shirts:red");
+        let jeans = Item::new("pants:jeans");
+        let slacks = Item::new("pants:slacks");
+
+        let shirts = Family::new("shirts");
+        let pants = Family::new("pants");
+
+        let closet = ClosetBuilder::new()
+            .add_item(&shirts, &blue)
+            .add_item(&shirts, &red)
+            .add_item(&pants, &jeans)
+            .add_item(&pants, &slacks)
+            .build()
+            .expect("expected build to return Closet");
+
+        {
+            let garbage = closet.select_item(&red).expect("expected select to return Closet");
+
+            assert_eq!(2, garbage.outfit_count());
+        }
+
+        let collection = collect();
+
+        assert!(collection.reclaimed > 0);
+        assert!(collection.live > 0);
+        assert_eq!(0, collect().reclaimed);
+
+        assert_eq!(4, closet.outfit_count());
+        assert_eq!(
+            Ok(Outfit::new(vec![jeans.clone(), blue.clone()])),
+            closet.complete_outfit(vec![blue.clone(), jeans.clone()])
+        );
+    }
+
+    #[test]
+    fn complete_outfit_refuses_conflicts_while_collect_runs() {
+        let blue = Item::new("shirts:blue");
+        let red = Item::new("shirts:red");
+        let jeans = Item::new("pants:jeans");
+        let slacks = Item::new("pants:slacks");
+
+        let shirts = Family::new("shirts");
+        let pants = Family::new("pants");
+
+        let closet = ClosetBuilder::new()
+            .add_item(&shirts, &blue)
+            .add_item(&shirts, &red)
+            .add_item(&pants, &jeans)
+            .add_item(&pants, &slacks)
+            .add_exclusion_rule(&blue, &jeans)
+            .must_build();
+
+        let done = Arc::new(AtomicBool::new(false));
+        let collector = {
+            let done = done.clone();
+            thread::spawn(move || while !done.load(Ordering::SeqCst) {
+                collect();
+            })
+        };
+
+        for _ in 0..1_000 {
+            assert_eq!(
+                Err(IncompatibleSelections(vec![jeans.clone(), blue.clone()])),
+                closet.complete_outfit(vec![blue.clone(), jeans.clone()])
+            );
+        }
+
+        done.store(true, Ordering::SeqCst);
+        collector.join().unwrap();
+    }
+}
@@ -0,0 +1,104 @@
+use std::fmt;
+use std::hash::Hash;
+
+use hashbrown::HashSet;
+
+/// Graph collects the nodes and edges of a decision diagram to write them out in Graphviz DOT
+///
+/// Low edges are dashed and high edges are solid, and anything highlighted is drawn in red
+pub(crate) struct Graph {
+    lines: Vec<String>,
+}
+
+impl Graph {
+    pub fn new() -> Self {
+        Graph { lines: vec![] }
+    }
+
+    pub fn branch(&mut self, name: usize, label: &str, highlighted: bool) {
+        let color = if highlighted { ", color=red" } else { "" };
+
+        self.lines.push(format!("{} [label=\"{}\"{}];", name, escape(label), color));
+    }
+
+    pub fn leaf(&mut self, name: usize, label: &str, highlighted: bool) {
+        let color = if highlighted { ", color=red" } else { "" };
+
+        self.lines.push(format!("{} [label=\"{}\", shape=box{}];", name, escape(label), color));
+    }
+
+    pub fn edge(&mut self, from: usize, to: usize, high: bool, highlighted: bool) {
+        let attributes = match (high, highlighted) {
+            (false, false) => " [style=dashed]",
+            (false, true) => " [style=dashed, color=red, penwidth=2]",
+            (true, false) => "",
+            (true, true) => " [color=red, penwidth=2]",
+        };
+
+        self.lines.push(format!("{} -> {}{};", from, to, attributes));
+    }
+}
+
+impl fmt::Display for Graph {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "digraph {{")?;
+        for line in &self.lines {
+            writeln!(f, "    {}", line)?;
+        }
+        write!(f, "}}")
+    }
+}
+
+fn escape(label: &str) -> String {
+    label.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Path remembers the nodes a walk down a diagram visits and which edge it takes out of each
+pub(crate) struct Path<I: Hash + Eq + Copy> {
+    nodes: HashSet<I>,
+    edges: HashSet<(I, bool)>,
+}
+
+impl<I: Hash + Eq + Copy> Default for Path<I> {
+    fn default() -> Self {
+        Path { nodes: HashSet::new(), edges: HashSet::new() }
+    }
+}
+
+impl<I: Hash + Eq + Copy> Path<I> {
+    pub fn visit(&mut self, node: I) {
+        self.nodes.insert(node);
+    }
+
+    pub fn take(&mut self, node: I, high: bool) {
+        self.nodes.insert(node);
+        self.edges.insert((node, high));
+    }
+
+    pub fn visits(&self, node: I) -> bool {
+        self.nodes.contains(&node)
+    }
+
+    pub fn takes(&self, node: I, high: bool) -> bool {
+        self.edges.contains(&(node, high))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Graph;
+
+    #[test]
+    fn graph_styles_edges_and_highlights() {
+        let mut graph = Graph::new();
+        graph.branch(0, "say \"hi\"", true);
+        graph.leaf(1, "Always", false);
+        graph.edge(0, 1, false, false);
+        graph.edge(0, 1, true, true);
+
+        assert_eq!(
+            "digraph {\n    0 [label=\"say \\\"hi\\\"\", color=red];\n    1 [label=\"Always\", shape=box];\n    0 -> 1 [style=dashed];\n    0 -> 1 [color=red, penwidth=2];\n}",
+            graph.to_string()
+        );
+    }
+}
@@ -7,6 +7,7 @@ extern crate lazy_static;
 extern crate maplit;
 #[macro_use]
 extern crate nom;
+extern crate rand;
 extern crate rayon;
 extern crate serde;
 #[macro_use]
@@ -19,8 +20,10 @@ pub use types::Forest;
 pub use types::Tree;
 pub use zdd::*;
 
+mod dot;
 mod types;
 mod status;
+mod table;
 pub mod zdd;
 pub mod zdd2;
 pub mod matrix;
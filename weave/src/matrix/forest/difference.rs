@@ -0,0 +1,83 @@
+use std::hash::Hash;
+
+use hashbrown::HashSet;
+
+use super::Forest;
+
+pub fn difference<T: Hash + Eq + Clone + Ord + Sync + Send>(forest1: Forest<T>, forest2: Forest<T>) -> Forest<T> {
+    if forest1 == forest2 {
+        return Forest::empty();
+    }
+
+    match (&forest1, &forest2) {
+        (Forest::Empty, _) => Forest::empty(),
+        (_, Forest::Empty) => forest1.clone(),
+
+        (_, _) => {
+            let matrix2: HashSet<Vec<T>> = Into::<Vec<_>>::into(&forest2).into_iter().collect();
+
+            let matrix = Into::<Vec<Vec<T>>>::into(forest1)
+                .into_iter()
+                .filter(|set| !matrix2.contains(set))
+                .collect::<Vec<_>>();
+
+            Forest::many(&matrix)
+        }
+    }
+}
+
+pub fn symmetric_difference<T: Hash + Eq + Clone + Ord + Sync + Send>(forest1: Forest<T>, forest2: Forest<T>) -> Forest<T> {
+    let left = difference(forest1.clone(), forest2.clone());
+    let right = difference(forest2, forest1);
+
+    Forest::union(left, right)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Forest;
+
+    #[test]
+    fn difference_returns_empty_when_forests_are_equal() {
+        let forest1 = Forest::many(&[vec!["1", "2"], vec!["2", "3"]]);
+        let forest2 = Forest::many(&[vec!["2", "3"], vec!["1", "2"]]);
+
+        assert_eq!(
+            Forest::<&str>::empty(),
+            Forest::difference(forest1, forest2)
+        );
+    }
+
+    #[test]
+    fn difference_returns_left_when_right_is_empty() {
+        let forest1 = Forest::unit(&["1", "2"]);
+        let forest2 = Forest::empty();
+
+        assert_eq!(
+            Forest::unit(&["1", "2"]),
+            Forest::difference(forest1, forest2)
+        );
+    }
+
+    #[test]
+    fn difference_removes_common_sets() {
+        let forest1 = Forest::many(&[vec!["1", "2"], vec!["2", "3"], vec!["3", "4"]]);
+        let forest2 = Forest::many(&[vec!["2", "3"], vec!["4", "5"]]);
+
+        assert_eq!(
+            Forest::many(&[vec!["1", "2"], vec!["3", "4"]]),
+            Forest::difference(forest1, forest2)
+        );
+    }
+
+    #[test]
+    fn symmetric_difference_keeps_sets_in_exactly_one() {
+        let forest1 = Forest::many(&[vec!["1", "2"], vec!["2", "3"]]);
+        let forest2 = Forest::many(&[vec!["2", "3"], vec!["4", "5"]]);
+
+        assert_eq!(
+            Forest::many(&[vec!["1", "2"], vec!["4", "5"]]),
+            Forest::symmetric_difference(forest1, forest2)
+        );
+    }
+}
@@ -8,6 +8,7 @@ mod union;
 mod intersect;
 mod subset;
 mod product;
+mod difference;
 
 /// Forest is an immutable set of sets
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -127,6 +128,14 @@ impl<T: Hash + Eq + Clone + Ord + Sync + Send> Forest<T> {
         product::product(self, other)
     }
 
+    pub fn difference(self, other: Self) -> Self {
+        difference::difference(self, other)
+    }
+
+    pub fn symmetric_difference(self, other: Self) -> Self {
+        difference::symmetric_difference(self, other)
+    }
+
     pub fn subset(self, element: T) -> Self {
         subset::subset(self, element)
     }
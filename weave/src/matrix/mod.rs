@@ -53,6 +53,14 @@ impl<T: Hash + Eq + Clone + Ord + Sync + Send> types::Forest<T> for Forest<T> {
         Forest::product(self, other)
     }
 
+    fn difference(self, other: Self) -> Self {
+        Forest::difference(self, other)
+    }
+
+    fn symmetric_difference(self, other: Self) -> Self {
+        Forest::symmetric_difference(self, other)
+    }
+
     fn subset(self, element: T) -> Self {
         Forest::subset(self, element)
     }
@@ -0,0 +1,200 @@
+use std::hash::Hash;
+
+use hashbrown::HashMap;
+
+/// The version written with every node table; the nested strings that came before it count as 1
+pub(crate) const VERSION: usize = 2;
+
+/// The ids a node table gives the false and true leaves, before any branch
+const FALSE: usize = 0;
+const TRUE: usize = 1;
+
+/// Entry is what a node table needs to know about a node to write it
+pub(crate) enum Entry<I> {
+    Leaf(bool),
+    Branch(usize, I, I),
+}
+
+/// NodeTable lists every node of a diagram once, children before parents, so shared nodes are not repeated
+///
+/// Each node is `(id, priority, low, high)`, where ids 0 and 1 are the false and true leaves
+#[derive(Clone, Eq, PartialEq, Debug, Serialize, Deserialize)]
+pub(crate) struct NodeTable {
+    version: usize,
+    nodes: Vec<(usize, usize, usize, usize)>,
+    root: usize,
+}
+
+impl NodeTable {
+    /// Whether `text` was written in the nested format, which every diagram used before node tables
+    pub fn is_nested(text: &str) -> bool {
+        text.trim_start().starts_with('(')
+    }
+
+    pub fn new(nodes: Vec<(usize, usize, usize, usize)>, root: usize) -> Self {
+        NodeTable { version: VERSION, nodes, root }
+    }
+
+    pub fn write<I, F>(root: I, expand: F) -> Self where I: Hash + Eq + Copy, F: Fn(I) -> Entry<I> {
+        let mut nodes = vec![];
+        let root = write_inner(root, &expand, &mut HashMap::new(), &mut nodes);
+
+        NodeTable::new(nodes, root)
+    }
+
+    pub fn nodes(&self) -> &[(usize, usize, usize, usize)] {
+        &self.nodes
+    }
+
+    pub fn root(&self) -> usize {
+        self.root
+    }
+
+    /// Rebuilds the diagram bottom up, refusing other versions and nodes that point forward or to a priority that is
+    /// not below them
+    pub fn read<N, L, B>(&self, leaf: L, mut branch: B) -> Result<N, String>
+        where N: Clone, L: Fn(bool) -> N, B: FnMut(usize, N, N) -> N {
+        if self.version != VERSION {
+            return Err(format!("Unsupported node table version: {}", self.version));
+        }
+
+        let mut built = vec![(None, leaf(false)), (None, leaf(true))];
+
+        for &(id, priority, low, high) in &self.nodes {
+            if id != built.len() {
+                return Err(format!("Expected node {} but found node {}", built.len(), id));
+            }
+
+            let low = child(&built, priority, low)?;
+            let high = child(&built, priority, high)?;
+            built.push((Some(priority), branch(priority, low, high)));
+        }
+
+        built.get(self.root)
+            .map(|(_, node)| node.clone())
+            .ok_or_else(|| format!("Root {} is not in the table", self.root))
+    }
+}
+
+fn write_inner<I, F>(node: I, expand: &F, ids: &mut HashMap<I, usize>, nodes: &mut Vec<(usize, usize, usize, usize)>) -> usize
+    where I: Hash + Eq + Copy, F: Fn(I) -> Entry<I> {
+    let (priority, low, high) = match expand(node) {
+        Entry::Leaf(false) => return FALSE,
+        Entry::Leaf(true) => return TRUE,
+        Entry::Branch(priority, low, high) => (priority, low, high),
+    };
+
+    if let Some(id) = ids.get(&node) {
+        return *id;
+    }
+
+    let low = write_inner(low, expand, ids, nodes);
+    let high = write_inner(high, expand, ids, nodes);
+
+    let id = nodes.len() + 2;
+    nodes.push((id, priority, low, high));
+    ids.insert(node, id);
+    id
+}
+
+fn child<N: Clone>(built: &[(Option<usize>, N)], parent: usize, id: usize) -> Result<N, String> {
+    match built.get(id) {
+        Some((Some(priority), _)) if *priority <= parent =>
+            Err(format!("Node {} has priority {}, which is not below its parent's {}", id, priority, parent)),
+        Some((_, node)) => Ok(node.clone()),
+        None => Err(format!("Node {} is used before it is defined", id)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_test::{assert_tokens, Token};
+
+    use super::Entry;
+    use super::NodeTable;
+
+    /// A diagram over node names, where `c` is shared by both edges of `a`
+    fn expand(name: char) -> Entry<char> {
+        match name {
+            'a' => Entry::Branch(0, 'b', 'c'),
+            'b' => Entry::Branch(1, 'F', 'c'),
+            'c' => Entry::Branch(2, 'F', 'T'),
+            leaf => Entry::Leaf(leaf == 'T'),
+        }
+    }
+
+    #[test]
+    fn shared_nodes_are_written_once() {
+        let table = NodeTable::write('a', expand);
+
+        assert_eq!(&[(2, 2, 0, 1), (3, 1, 0, 2), (4, 0, 3, 2)], table.nodes());
+        assert_eq!(4, table.root());
+    }
+
+    #[test]
+    fn leaves_are_written_without_nodes() {
+        assert_eq!(NodeTable::new(vec![], 1), NodeTable::write('T', expand));
+        assert_eq!(NodeTable::new(vec![], 0), NodeTable::write('F', expand));
+    }
+
+    #[test]
+    fn tables_serialize_as_a_struct() {
+        assert_tokens(&NodeTable::write('c', expand), &[
+            Token::Struct { name: "NodeTable", len: 3 },
+            Token::Str("version"),
+            Token::U64(2),
+            Token::Str("nodes"),
+            Token::Seq { len: Some(1) },
+            Token::Tuple { len: 4 },
+            Token::U64(2),
+            Token::U64(2),
+            Token::U64(0),
+            Token::U64(1),
+            Token::TupleEnd,
+            Token::SeqEnd,
+            Token::Str("root"),
+            Token::U64(2),
+            Token::StructEnd,
+        ]);
+    }
+
+    #[test]
+    fn tables_read_back_what_was_written() {
+        let table = NodeTable::write('a', expand);
+
+        let text = table.read(
+            |leaf| if leaf { String::from("T") } else { String::from("F") },
+            |priority, low, high| format!("({} {} {})", priority, low, high),
+        );
+
+        assert_eq!(Ok(String::from("(0 (1 F (2 F T)) (2 F T))")), text);
+    }
+
+    #[test]
+    fn nested_strings_are_told_apart_from_tables() {
+        assert!(NodeTable::is_nested(" (0 (F) (T))"));
+        assert!(!NodeTable::is_nested("0 F T"));
+    }
+
+    #[test]
+    fn unknown_versions_are_refused() {
+        let table = NodeTable { version: 3, nodes: vec![], root: 1 };
+
+        assert_eq!(
+            Err(String::from("Unsupported node table version: 3")),
+            table.read(|leaf| leaf as usize, |priority, _, _| priority)
+        );
+    }
+
+    #[test]
+    fn malformed_tables_are_refused() {
+        let read = |nodes: Vec<(usize, usize, usize, usize)>, root: usize| NodeTable::new(nodes, root)
+            .read(|leaf| leaf as usize, |priority, _, _| priority);
+
+        assert!(read(vec![(3, 0, 0, 1)], 3).is_err());
+        assert!(read(vec![(2, 0, 0, 3)], 2).is_err());
+        assert!(read(vec![(2, 1, 0, 1), (3, 1, 2, 1)], 3).is_err());
+        assert!(read(vec![(2, 0, 0, 1)], 5).is_err());
+        assert_eq!(Ok(0), read(vec![(2, 0, 0, 1)], 2));
+    }
+}
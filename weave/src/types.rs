@@ -15,6 +15,8 @@ pub trait Forest<T: Clone> {
     fn intersect(self, other: Self) -> Self;
     fn union(self, other: Self) -> Self;
     fn product(self, other: Self) -> Self;
+    fn difference(self, other: Self) -> Self;
+    fn symmetric_difference(self, other: Self) -> Self;
 
     fn subset(self, element: T) -> Self;
     fn subset_not(self, element: T) -> Self;
@@ -1,6 +1,8 @@
 mod node;
 mod tree;
 
+pub use self::node::collect;
+pub use self::node::Collection;
 pub use self::tree::Universe;
 pub use self::tree::Tree;
 pub use self::tree::TreeNode;
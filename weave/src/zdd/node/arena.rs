@@ -4,15 +4,29 @@ use hashbrown::HashMap;
 use zdd::node::Node;
 use zdd::node::NodeId;
 
+/// Collection reports what a garbage collection pass over an arena reclaimed
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub struct Collection {
+    pub reclaimed: usize,
+    pub live: usize,
+}
+
 #[derive(Clone, Eq, PartialEq, Debug)]
 pub struct NodeArena {
-    nodes: Vec<Node>,
+    nodes: Vec<Option<Node>>,
     node_index: HashMap<Node, NodeId>,
+    free: Vec<NodeId>,
+    roots: HashMap<NodeId, usize>,
 }
 
 impl Default for NodeArena {
     fn default() -> Self {
-        let mut arena = NodeArena { nodes: Vec::new(), node_index: HashMap::new() };
+        let mut arena = NodeArena {
+            nodes: Vec::new(),
+            node_index: HashMap::new(),
+            free: Vec::new(),
+            roots: HashMap::new(),
+        };
         arena.add(Node::Leaf(false));
         arena.add(Node::Leaf(true));
         arena
@@ -28,21 +42,78 @@ impl NodeArena {
         match self.node_index.entry(node) {
             Occupied(entry) => *entry.get(),
             Vacant(entry) => {
-                let index = NodeId(self.nodes.len());
+                let index = match self.free.pop() {
+                    Some(index) => {
+                        self.nodes[index.0] = Some(node);
+                        index
+                    }
+                    None => {
+                        self.nodes.push(Some(node));
+                        NodeId(self.nodes.len() - 1)
+                    }
+                };
 
-                self.nodes.push(node);
                 *entry.insert(index)
             }
         }
     }
 
     pub fn get(&self, index: NodeId) -> Option<&Node> {
-        self.nodes.get(index.0)
+        self.nodes.get(index.0).and_then(Option::as_ref)
+    }
+
+    /// Keeps `root`, and every node below it, alive until a matching `release`
+    pub fn retain(&mut self, root: NodeId) {
+        *self.roots.entry(root).or_insert(0) += 1;
+    }
+
+    pub fn release(&mut self, root: NodeId) {
+        if let Occupied(mut entry) = self.roots.entry(root) {
+            *entry.get_mut() -= 1;
+            if *entry.get() == 0 {
+                entry.remove();
+            }
+        }
+    }
+
+    /// Frees every branch that is not reachable from a retained root
+    pub fn collect(&mut self) -> Collection {
+        let mut marked = vec![false; self.nodes.len()];
+
+        let mut queue: Vec<NodeId> = self.roots.keys().cloned().collect();
+        while let Some(id) = queue.pop() {
+            if marked[id.0] {
+                continue;
+            }
+
+            marked[id.0] = true;
+            if let Some(Node::Branch(_, low, high)) = self.nodes[id.0] {
+                queue.push(low);
+                queue.push(high);
+            }
+        }
+
+        let mut reclaimed = 0;
+        for (index, marked) in marked.into_iter().enumerate() {
+            if marked {
+                continue;
+            }
+
+            if let Some(Node::Branch(..)) = self.nodes[index] {
+                let node = self.nodes[index].take().unwrap();
+                self.node_index.remove(&node);
+                self.free.push(NodeId(index));
+                reclaimed += 1;
+            }
+        }
+
+        Collection { reclaimed, live: self.node_index.len() }
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use super::Collection;
     use super::Node;
     use super::NodeArena;
 
@@ -101,4 +172,50 @@ mod tests {
 
         assert_eq!(node1_id, node2_id);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn collect_frees_unreachable_nodes() {
+        let mut arena = NodeArena::new();
+
+        let leaf_id = arena.add(Node::Branch(1, Node::FALSE, Node::TRUE));
+        let root_id = arena.add(Node::Branch(0, Node::FALSE, leaf_id));
+        let garbage_id = arena.add(Node::Branch(2, Node::FALSE, Node::TRUE));
+
+        arena.retain(root_id);
+        let collection = arena.collect();
+
+        assert_eq!(Collection { reclaimed: 1, live: 4 }, collection);
+        assert!(arena.get(root_id).is_some());
+        assert!(arena.get(leaf_id).is_some());
+        assert!(arena.get(garbage_id).is_none());
+    }
+
+    #[test]
+    fn collect_keeps_roots_until_every_retain_is_released() {
+        let mut arena = NodeArena::new();
+
+        let root_id = arena.add(Node::Branch(0, Node::FALSE, Node::TRUE));
+
+        arena.retain(root_id);
+        arena.retain(root_id);
+        arena.release(root_id);
+        assert_eq!(0, arena.collect().reclaimed);
+
+        arena.release(root_id);
+        assert_eq!(1, arena.collect().reclaimed);
+    }
+
+    #[test]
+    fn add_reuses_freed_ids() {
+        let mut arena = NodeArena::new();
+
+        let garbage_id = arena.add(Node::Branch(0, Node::FALSE, Node::TRUE));
+        arena.collect();
+
+        let node = Node::Branch(1, Node::FALSE, Node::TRUE);
+        let node_id = arena.add(node);
+
+        assert_eq!(garbage_id, node_id);
+        assert_eq!(Some(&node), arena.get(node_id));
+    }
+}
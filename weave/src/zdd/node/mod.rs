@@ -1,10 +1,14 @@
 pub use self::arena::*;
+pub use self::root::Root;
+use std::cell::Cell;
 use std::fmt;
 use std::sync::RwLock;
+use std::sync::RwLockReadGuard;
 
 mod arena;
 mod serialize;
 mod parser;
+mod root;
 
 #[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
 pub struct NodeId(usize);
@@ -98,3 +102,46 @@ lazy_static! {
         RwLock::new(a)
     };
 }
+
+lazy_static! {
+    static ref OPERATIONS: RwLock<()> = RwLock::new(());
+}
+
+thread_local! {
+    static OPERATION_DEPTH: Cell<usize> = const { Cell::new(0) };
+}
+
+/// OperationGuard holds off `collect` while an operation works with nodes that are not yet rooted
+pub struct OperationGuard {
+    _operations: Option<RwLockReadGuard<'static, ()>>,
+}
+
+impl Drop for OperationGuard {
+    fn drop(&mut self) {
+        OPERATION_DEPTH.with(|depth| depth.set(depth.get() - 1));
+    }
+}
+
+/// Marks the current thread as working with nodes until the returned guard is dropped
+///
+/// Guards nest, so only the outermost one on a thread waits for a running collection
+pub fn operation() -> OperationGuard {
+    let operations = OPERATION_DEPTH.with(|depth| {
+        let outermost = depth.get() == 0;
+        depth.set(depth.get() + 1);
+
+        if outermost { Some(OPERATIONS.read().unwrap()) } else { None }
+    });
+
+    OperationGuard { _operations: operations }
+}
+
+/// Frees every node that is not reachable from a live `Tree`
+///
+/// Waits for running operations to finish, so it must not be called from inside one
+pub fn collect() -> Collection {
+    let _operations = OPERATIONS.write().unwrap();
+
+    ARENA.write().unwrap().collect()
+}
+
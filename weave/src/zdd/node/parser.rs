@@ -6,19 +6,6 @@ use zdd::node::Node;
 const TRUE_NODE: &str = "(T)";
 const FALSE_NODE: &str = "(F)";
 
-pub(crate) fn build_node_string(node: Node) -> String {
-    match node {
-        Node::Leaf(true) => String::from(TRUE_NODE),
-        Node::Leaf(false) => String::from(FALSE_NODE),
-        Node::Branch(id, low, high) => format!(
-            "({:?} {} {})",
-            id,
-            build_node_string(Node::from(low)),
-            build_node_string(Node::from(high))
-        ),
-    }
-}
-
 pub(crate) fn parse_node_string(node_str: &str) -> Result<Node, Err<&[u8], u32>> {
     node(node_str.as_bytes())
         .map(|(_, node)| node)
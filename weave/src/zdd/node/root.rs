@@ -0,0 +1,78 @@
+use std::fmt;
+
+use serde::de::{Deserialize, Deserializer};
+use serde::ser::{Serialize, Serializer};
+
+use zdd::node::ARENA;
+use zdd::node::NodeId;
+use zdd::node::operation;
+
+/// Root retains a node in the arena, keeping it and everything below it alive across collections
+#[derive(Eq, PartialEq, Hash)]
+pub struct Root(NodeId);
+
+impl Root {
+    pub fn new(node: impl Into<NodeId>) -> Self {
+        let id = node.into();
+        ARENA.write().unwrap().retain(id);
+
+        Root(id)
+    }
+
+    pub fn id(&self) -> NodeId {
+        self.0
+    }
+}
+
+impl Clone for Root {
+    fn clone(&self) -> Self {
+        Root::new(self.0)
+    }
+}
+
+impl Drop for Root {
+    fn drop(&mut self) {
+        if let Ok(mut arena) = ARENA.write() {
+            arena.release(self.0);
+        }
+    }
+}
+
+impl fmt::Debug for Root {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self.0)
+    }
+}
+
+impl Serialize for Root {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Root {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let _operation = operation();
+
+        NodeId::deserialize(deserializer).map(Root::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zdd::node::ARENA;
+    use zdd::node::Node;
+    use zdd::node::Root;
+
+    #[test]
+    fn root_is_retained_until_every_clone_is_dropped() {
+        let node = Node::required_branch(12_345, Node::TRUE);
+
+        let root = Root::new(node);
+        let clone = root.clone();
+        drop(root);
+
+        assert!(ARENA.read().unwrap().get(clone.id()).is_some());
+        assert_eq!(node, clone.id().into());
+    }
+}
@@ -1,6 +1,9 @@
-use serde::de::{Deserialize, Deserializer, Error, Visitor};
+use serde::de::{Deserialize, Deserializer, Error, MapAccess, Unexpected, Visitor};
+use serde::de::value::MapAccessDeserializer;
 use serde::ser::{Serialize, Serializer};
 use std::fmt;
+use table::Entry;
+use table::NodeTable;
 use zdd::node::Node;
 use zdd::node::NodeId;
 use zdd::node::parser;
@@ -14,8 +17,12 @@ impl Serialize for NodeId {
 
 impl Serialize for Node {
     fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
-        let node_str = parser::build_node_string(*self);
-        serializer.serialize_newtype_struct("Node", &node_str)
+        let table = NodeTable::write(NodeId::from(self), |node_id| match Node::from(node_id) {
+            Node::Branch(priority, low, high) => Entry::Branch(priority, low, high),
+            Node::Leaf(leaf) => Entry::Leaf(leaf),
+        });
+
+        serializer.serialize_newtype_struct("Node", &table)
     }
 }
 
@@ -29,23 +36,43 @@ impl<'de> Visitor<'de> for NodeVisitor {
     }
 
     fn visit_newtype_struct<D: Deserializer<'de>>(self, deserializer: D) -> Result<Node, D::Error> {
-        deserializer.deserialize_str(NodeStringVisitor)
+        if deserializer.is_human_readable() {
+            return deserializer.deserialize_any(NodeTableVisitor);
+        }
+
+        NodeTable::deserialize(deserializer)
+            .and_then(read_table)
     }
 }
 
-struct NodeStringVisitor;
+/// NodeTableVisitor reads a node table, or the nested string that self-describing formats held before node tables
+struct NodeTableVisitor;
 
-impl<'de> Visitor<'de> for NodeStringVisitor {
+impl<'de> Visitor<'de> for NodeTableVisitor {
     type Value = Node;
 
     fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-        formatter.write_str("string, formatted as a Node")
+        formatter.write_str("struct NodeTable or a nested Node string")
     }
 
     fn visit_str<E: Error>(self, node_str: &str) -> Result<Node, E> {
+        if !NodeTable::is_nested(node_str) {
+            return Err(Error::invalid_value(Unexpected::Str(node_str), &self));
+        }
+
         parser::parse_node_string(node_str)
             .map_err(Error::custom)
     }
+
+    fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Node, A::Error> {
+        NodeTable::deserialize(MapAccessDeserializer::new(map))
+            .and_then(read_table)
+    }
+}
+
+fn read_table<E: Error>(table: NodeTable) -> Result<Node, E> {
+    table.read(Node::Leaf, Node::branch)
+        .map_err(Error::custom)
 }
 
 impl<'de> Deserialize<'de> for NodeId {
@@ -0,0 +1,109 @@
+use std::fmt;
+use std::hash::Hash;
+
+use hashbrown::HashMap;
+
+use dot::Graph;
+use dot::Path;
+use zdd::node::Node;
+use zdd::node::NodeId;
+use zdd::node::operation;
+use zdd::tree::Tree;
+
+/// Writes every node reachable from the tree's root once, however many branches share it
+pub fn to_dot<T>(tree: &Tree<T>, highlight: Option<&[T]>) -> String where T: Clone + Ord + Hash + fmt::Display {
+    let _operation = operation();
+
+    let path = highlight
+        .map(|combination| path(tree, combination))
+        .unwrap_or_default();
+
+    let mut graph = Graph::new();
+    visit(tree, tree.root.id(), &path, &mut HashMap::new(), &mut graph);
+    graph.to_string()
+}
+
+/// Follows the high edge of every item in `combination` and the low edge of every other item
+fn path<T>(tree: &Tree<T>, combination: &[T]) -> Path<NodeId> where T: Clone + Ord + Hash {
+    let mut path = Path::default();
+    let mut id = tree.root.id();
+    while let Node::Branch(priority, low, high) = Node::from(id) {
+        let take_high = tree.universe.get_item(priority)
+            .is_some_and(|item| combination.contains(&item));
+        path.take(id, take_high);
+        id = if take_high { high } else { low };
+    }
+    path.visit(id);
+
+    path
+}
+
+fn visit<T>(tree: &Tree<T>, id: NodeId, path: &Path<NodeId>, names: &mut HashMap<NodeId, usize>, graph: &mut Graph) -> usize
+    where T: Clone + Ord + Hash + fmt::Display {
+    if let Some(name) = names.get(&id) {
+        return *name;
+    }
+
+    let name = names.len();
+    names.insert(id, name);
+
+    match Node::from(id) {
+        Node::Branch(priority, low, high) => {
+            let label = tree.universe.get_item(priority).unwrap().to_string();
+            graph.branch(name, &label, path.visits(id));
+
+            let low_name = visit(tree, low, path, names, graph);
+            let high_name = visit(tree, high, path, names, graph);
+            graph.edge(name, low_name, false, path.takes(id, false));
+            graph.edge(name, high_name, true, path.takes(id, true));
+        }
+        Node::Leaf(value) => graph.leaf(name, &value.to_string(), path.visits(id)),
+    }
+
+    name
+}
+
+#[cfg(test)]
+mod tests {
+    use zdd::tree::Universe;
+
+    #[test]
+    fn to_dot_labels_branches_with_items() {
+        let universe = Universe::from(vec!["1", "2"]);
+        let tree = universe.tree(&["1", "2"]).union(&universe.tree(&["2"]));
+
+        assert_eq!(
+            "digraph {
+    0 [label=\"1\"];
+    1 [label=\"2\"];
+    2 [label=\"false\", shape=box];
+    3 [label=\"true\", shape=box];
+    1 -> 2 [style=dashed];
+    1 -> 3;
+    0 -> 1 [style=dashed];
+    0 -> 1;
+}",
+            tree.to_dot()
+        );
+    }
+
+    #[test]
+    fn to_dot_highlighting_marks_the_path_of_a_combination() {
+        let universe = Universe::from(vec!["1", "2"]);
+        let tree = universe.tree(&["1", "2"]).union(&universe.tree(&["2"]));
+
+        assert_eq!(
+            "digraph {
+    0 [label=\"1\", color=red];
+    1 [label=\"2\", color=red];
+    2 [label=\"false\", shape=box];
+    3 [label=\"true\", shape=box, color=red];
+    1 -> 2 [style=dashed];
+    1 -> 3 [color=red, penwidth=2];
+    0 -> 1 [style=dashed, color=red, penwidth=2];
+    0 -> 1;
+}",
+            tree.to_dot_highlighting(&["2"])
+        );
+    }
+}
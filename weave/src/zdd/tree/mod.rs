@@ -8,11 +8,14 @@ use itertools::Itertools;
 use status::ItemStatus;
 use zdd::node::Node;
 use zdd::node::NodeId;
+use zdd::node::operation;
+use zdd::node::Root;
 
 pub use self::traversable::TreeNode;
 pub use self::universe::*;
 
 mod combinations;
+mod dot;
 mod intersect;
 mod product;
 #[cfg(test)]
@@ -25,7 +28,7 @@ mod universe;
 
 #[derive(Clone, Eq, PartialEq, Serialize, Deserialize)]
 pub struct Tree<T: Ord + Hash + Eq> {
-    root: NodeId,
+    root: Root,
     universe: Universe<T>,
 }
 
@@ -37,15 +40,15 @@ impl<T: Ord + Hash + Eq> fmt::Debug for Tree<T> {
 
 impl<T: Clone + Ord + Hash> Tree<T> {
     fn empty(universe: Universe<T>) -> Tree<T> {
-        Tree { root: Node::FALSE, universe }
+        Tree { root: Root::new(Node::FALSE), universe }
     }
 
     fn unit(universe: Universe<T>) -> Tree<T> {
-        Tree { root: Node::TRUE, universe }
+        Tree { root: Root::new(Node::TRUE), universe }
     }
 
     fn from_root<R>(universe: Universe<T>, root: R) -> Tree<T> where R: Into<NodeId> {
-        Tree { root: root.into(), universe }
+        Tree { root: Root::new(root), universe }
     }
 
     pub fn summarize(&self, inclusions: &[T], exclusions: &[T]) -> Vec<ItemStatus<T>> {
@@ -79,14 +82,14 @@ impl<T: Clone + Ord + Hash> Tree<T> {
     }
 
     pub fn combinations_recursive(&self) -> BTreeSet<BTreeSet<T>> {
-        combinations::combinations_recursive(self.root)
+        combinations::combinations_recursive(self.root.id())
             .into_iter()
             .map(|set| self.universe.get_items(&set))
             .collect::<BTreeSet<_>>()
     }
 
     pub fn combinations(&self) -> BTreeSet<BTreeSet<T>> {
-        combinations::combinations_iter(self.root)
+        combinations::combinations_iter(self.root.id())
             .into_iter()
             .map(|set| self.universe.get_items(&set))
             .collect::<BTreeSet<_>>()
@@ -96,7 +99,7 @@ impl<T: Clone + Ord + Hash> Tree<T> {
         let inclusions = inclusions.iter().cloned().collect::<BTreeSet<_>>();
         let exclusions = exclusions.iter().cloned().collect::<BTreeSet<_>>();
 
-        combinations::combinations_iter(self.root)
+        combinations::combinations_iter(self.root.id())
             .into_iter()
             .map(|set| self.universe.get_items(&set))
             .filter(|set| set.intersection(&inclusions).cloned().collect::<BTreeSet<_>>() == inclusions)
@@ -104,26 +107,42 @@ impl<T: Clone + Ord + Hash> Tree<T> {
             .collect::<BTreeSet<_>>()
     }
 
+    /// The diagram behind this tree in Graphviz DOT, with each node written once
+    pub fn to_dot(&self) -> String where T: fmt::Display {
+        dot::to_dot(self, None)
+    }
+
+    /// Like `to_dot`, with the edges that looking up `combination` follows drawn in red
+    pub fn to_dot_highlighting(&self, combination: &[T]) -> String where T: fmt::Display {
+        dot::to_dot(self, Some(combination))
+    }
+
     pub fn union(&self, other: &Tree<T>) -> Tree<T> {
+        let _operation = operation();
+
         let root = union::union(
-            self.root.into(),
-            other.root.into());
+            self.root.id().into(),
+            other.root.id().into());
 
         Tree::from_root(self.universe.clone(), root)
     }
 
     pub fn intersect(&self, other: &Tree<T>) -> Tree<T> {
+        let _operation = operation();
+
         let root = intersect::intersect(
-            self.root.into(),
-            other.root.into());
+            self.root.id().into(),
+            other.root.id().into());
 
         Tree::from_root(self.universe.clone(), root)
     }
 
     pub fn product(&self, other: &Tree<T>) -> Tree<T> {
+        let _operation = operation();
+
         let root = product::product(
-            self.root.into(),
-            other.root.into());
+            self.root.id().into(),
+            other.root.id().into());
 
         Tree::from_root(self.universe.clone(), root)
     }
@@ -13,13 +13,13 @@ pub enum TreeNode<T: Clone + Ord + Hash + Eq> {
 
 impl<T: Clone + Ord + Hash> From<Tree<T>> for TreeNode<T> {
     fn from(tree: Tree<T>) -> Self {
-        from_node(&tree.universe, tree.root)
+        from_node(&tree.universe, tree.root.id())
     }
 }
 
 impl<T: Clone + Ord + Hash> From<&Tree<T>> for TreeNode<T> {
     fn from(tree: &Tree<T>) -> Self {
-        from_node(&tree.universe, tree.root)
+        from_node(&tree.universe, tree.root.id())
     }
 }
 
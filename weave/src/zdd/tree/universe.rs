@@ -5,7 +5,7 @@ use hashbrown::HashMap;
 use itertools::Itertools;
 use serde::{Serialize, Serializer};
 
-use zdd::node::{Node, NodeId, Priority};
+use zdd::node::{Node, NodeId, operation, Priority};
 use zdd::tree::Tree;
 
 #[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
@@ -47,6 +47,8 @@ impl<T: Clone + Ord + Hash> Universe<T> {
     }
 
     pub fn tree(&self, combination: &[T]) -> Tree<T> {
+        let _operation = operation();
+
         let root = combination.iter()
             .filter_map(|item| self.item_index.get(&item))
             .cloned()
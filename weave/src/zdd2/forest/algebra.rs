@@ -0,0 +1,191 @@
+#[cfg(test)]
+mod quotient_tests {
+    use super::super::Forest;
+
+    #[test]
+    fn quotient_by_unit_keeps_matching_remainders() {
+        let forest = Forest::many(&[
+            vec!["a", "b", "c"],
+            vec!["a", "b", "d"],
+            vec!["b", "c"],
+            vec!["a", "e"],
+        ]);
+
+        assert_eq!(
+            Forest::many(&[
+                vec!["c"],
+                vec!["d"],
+            ]),
+            forest.quotient(Forest::unit(&["a", "b"]))
+        );
+    }
+
+    #[test]
+    fn quotient_by_many_keeps_common_remainders() {
+        let forest = Forest::many(&[
+            vec!["a", "b", "c"],
+            vec!["a", "b", "d"],
+            vec!["b", "c"],
+            vec!["a", "e"],
+        ]);
+
+        assert_eq!(
+            Forest::unit(&["c"]),
+            forest.quotient(Forest::many(&[
+                vec!["a", "b"],
+                vec!["b"],
+            ]))
+        );
+    }
+
+    #[test]
+    fn quotient_by_unknown_item_is_empty() {
+        let forest = Forest::many(&[
+            vec!["a", "b", "c"],
+            vec!["a", "b", "d"],
+            vec!["b", "c"],
+            vec!["a", "e"],
+        ]);
+
+        assert_eq!(
+            Forest::empty(),
+            forest.quotient(Forest::unit(&["f"]))
+        );
+    }
+
+    #[test]
+    fn quotient_by_empty_is_empty() {
+        let forest = Forest::many(&[
+            vec!["a", "b", "c"],
+            vec!["a", "b", "d"],
+            vec!["b", "c"],
+            vec!["a", "e"],
+        ]);
+
+        assert_eq!(
+            Forest::empty(),
+            forest.quotient(Forest::empty())
+        );
+    }
+
+    #[test]
+    fn quotient_of_empty_by_empty_is_empty() {
+        assert_eq!(
+            Forest::<&str>::empty(),
+            Forest::empty().quotient(Forest::empty())
+        );
+    }
+
+    #[test]
+    fn remainder_keeps_sets_outside_the_product() {
+        let forest = Forest::many(&[
+            vec!["a", "b", "c"],
+            vec!["a", "b", "d"],
+            vec!["b", "c"],
+            vec!["a", "e"],
+        ]);
+
+        assert_eq!(
+            Forest::many(&[
+                vec!["b", "c"],
+                vec!["a", "e"],
+            ]),
+            forest.remainder(Forest::unit(&["a", "b"]))
+        );
+    }
+
+    #[test]
+    fn quotient_and_remainder_rebuild_the_dividend() {
+        let forest = Forest::many(&[
+            vec!["a", "b", "c"],
+            vec!["a", "b", "d"],
+            vec!["b", "c"],
+            vec!["a", "e"],
+        ]);
+
+        let divisor = Forest::many(&[
+            vec!["a", "b"],
+            vec!["b"],
+        ]);
+
+        let quotient = forest.clone().quotient(divisor.clone());
+        let remainder = forest.clone().remainder(divisor.clone());
+
+        assert_eq!(forest, divisor.product(quotient).union(remainder));
+    }
+}
+
+#[cfg(test)]
+mod meet_tests {
+    use super::super::Forest;
+
+    #[test]
+    fn meet_of_empty_is_empty() {
+        assert_eq!(
+            Forest::empty(),
+            Forest::unit(&["a", "b"]).meet(Forest::empty())
+        );
+    }
+
+    #[test]
+    fn meet_keeps_every_pairwise_intersection() {
+        let forest1 = Forest::many(&[
+            vec!["a", "b", "c"],
+            vec!["c", "d"],
+        ]);
+        let forest2 = Forest::many(&[
+            vec!["b", "c", "e"],
+            vec!["a"],
+        ]);
+
+        let mut trees = forest1.meet(forest2).trees();
+        trees.sort();
+
+        assert_eq!(
+            vec![
+                vec![],
+                vec!["a"],
+                vec!["b", "c"],
+                vec!["c"],
+            ],
+            trees
+        );
+    }
+}
+
+#[cfg(test)]
+mod disjoint_product_tests {
+    use super::super::Forest;
+
+    #[test]
+    fn disjoint_product_skips_overlapping_pairs() {
+        let forest1 = Forest::many(&[
+            vec!["a", "b"],
+            vec!["c"],
+        ]);
+        let forest2 = Forest::many(&[
+            vec!["b", "d"],
+            vec!["e"],
+        ]);
+
+        assert_eq!(
+            Forest::many(&[
+                vec!["a", "b", "e"],
+                vec!["b", "c", "d"],
+                vec!["c", "e"],
+            ]),
+            forest1.disjoint_product(forest2)
+        );
+    }
+
+    #[test]
+    fn disjoint_product_is_within_product() {
+        let forest1 = Forest::unique(&["a", "b", "c"]);
+        let forest2 = Forest::unique(&["b", "c", "d"]);
+
+        let disjoint = forest1.clone().disjoint_product(forest2.clone());
+
+        assert_eq!(6, disjoint.len());
+        assert_eq!(disjoint.clone(), disjoint.intersect(forest1.product(forest2)));
+    }
+}
@@ -0,0 +1,343 @@
+use std::fmt;
+use std::hash::Hash;
+use std::io;
+use std::io::{ErrorKind, Read, Write};
+use std::str::FromStr;
+use std::sync::Arc;
+
+use hashbrown::HashMap;
+
+use table::Entry;
+use table::NodeTable;
+
+use super::Forest;
+use super::Manager;
+use super::Node;
+use super::NodeId;
+use super::Priority;
+use super::Root;
+use super::universe::Universe;
+
+const MAGIC: &[u8] = b"ZDD2";
+const VERSION: u64 = 1;
+
+/// Writes the magic bytes and version, the universe's items with their occurrences, the node table and its root,
+/// and then a checksum of everything before it
+///
+/// Numbers are unsigned LEB128 varints, and items are written as their `Display` text
+pub fn write_to<T, W>(forest: &Forest<T>, mut writer: W) -> io::Result<()>
+    where T: Hash + Eq + Clone + Ord + Sync + Send + fmt::Display, W: Write {
+    let _operation = forest.manager().operation();
+
+    let (root, universe) = if forest.universe.is_ordered() {
+        forest.default_order()
+    } else {
+        (Node::from(forest.root.id()), (*forest.universe).clone())
+    };
+
+    let table = NodeTable::write(NodeId::from(root), |node_id| match Node::from(node_id) {
+        Node::Branch(priority, low, high) => Entry::Branch(priority.0, low, high),
+        Node::Always => Entry::Leaf(true),
+        Node::Never => Entry::Leaf(false),
+    });
+
+    let mut bytes = MAGIC.to_vec();
+    write_number(&mut bytes, VERSION);
+
+    write_number(&mut bytes, universe.len() as u64);
+    for item in universe.items() {
+        let text = item.to_string();
+        write_number(&mut bytes, text.len() as u64);
+        bytes.extend_from_slice(text.as_bytes());
+        write_number(&mut bytes, universe.occurrences()[item] as u64);
+    }
+
+    write_number(&mut bytes, table.nodes().len() as u64);
+    for &(_, priority, low, high) in table.nodes() {
+        write_number(&mut bytes, priority as u64);
+        write_number(&mut bytes, low as u64);
+        write_number(&mut bytes, high as u64);
+    }
+    write_number(&mut bytes, table.root() as u64);
+
+    let checksum = checksum(&bytes);
+    bytes.extend_from_slice(&checksum.to_le_bytes());
+
+    writer.write_all(&bytes)
+}
+
+/// Reads a forest written by `write_to`, refusing anything that is corrupt, does not form a reduced diagram, or
+/// holds occurrences that its diagram does not
+pub fn read_from<T, R>(mut reader: R, manager: &Manager) -> io::Result<Forest<T>>
+    where T: Hash + Eq + Clone + Ord + Sync + Send + FromStr, R: Read {
+    let mut bytes = vec![];
+    reader.read_to_end(&mut bytes)?;
+
+    if bytes.len() < MAGIC.len() + 8 || !bytes.starts_with(MAGIC) {
+        return Err(invalid("Expected a forest, but the magic bytes are missing"));
+    }
+
+    let (body, checksum_bytes) = bytes.split_at(bytes.len() - 8);
+    let mut expected = [0; 8];
+    expected.copy_from_slice(checksum_bytes);
+    if checksum(body) != u64::from_le_bytes(expected) {
+        return Err(invalid("Checksum does not match, the forest is corrupt"));
+    }
+
+    let mut input = Input { bytes: &body[MAGIC.len()..] };
+    let version = input.number()?;
+    if version != VERSION {
+        return Err(invalid(format!("Unsupported forest version: {}", version)));
+    }
+
+    let item_count = input.length()?;
+    let mut items = Vec::with_capacity(item_count.min(input.bytes.len()));
+    let mut occurrences = HashMap::new();
+    for _ in 0..item_count {
+        let length = input.length()?;
+        let text = input.take(length)?;
+        let text = std::str::from_utf8(text)
+            .map_err(|_| invalid("Item is not valid UTF-8"))?;
+        let item = T::from_str(text)
+            .map_err(|_| invalid(format!("Item could not be parsed: '{}'", text)))?;
+
+        if occurrences.insert(item.clone(), input.length()?).is_some() {
+            return Err(invalid(format!("Item is repeated: '{}'", text)));
+        }
+        items.push(item);
+    }
+
+    let node_count = input.length()?;
+    let mut nodes = Vec::with_capacity(node_count.min(input.bytes.len()));
+    for index in 0..node_count {
+        let priority = input.length()?;
+        if priority >= item_count {
+            return Err(invalid(format!("Node {} tests item {}, but there are only {}", index + 2, priority, item_count)));
+        }
+
+        let (low, high) = (input.length()?, input.length()?);
+        if high == 0 {
+            return Err(invalid(format!("Node {} has a high edge to the empty family", index + 2)));
+        }
+
+        nodes.push((index + 2, priority, low, high));
+    }
+    let root = input.length()?;
+
+    if !input.bytes.is_empty() {
+        return Err(invalid("Unexpected bytes after the root"));
+    }
+
+    let universe = Universe::from_occurrences(occurrences);
+    if universe.items() != items.as_slice() {
+        return Err(invalid("Items are not in the order of their occurrences"));
+    }
+
+    let _operation = manager.operation();
+
+    let root = NodeTable::new(nodes, root)
+        .read(
+            |always| if always { Node::Always } else { Node::Never },
+            |priority, low, high| Node::branch(Priority(priority), low, high),
+        )
+        .map_err(invalid)?;
+
+    let counted = root.occurrences();
+    let miscounted = universe.items().iter()
+        .enumerate()
+        .find(|(index, item)| counted.get(&Priority(*index)).cloned().unwrap_or(0) != universe.occurrences()[item]);
+    if let Some((index, _)) = miscounted {
+        return Err(invalid(format!("Item {} does not occur as often as the forest says", index)));
+    }
+
+    Ok(Forest { root: Root::new(root), universe: Arc::new(universe) })
+}
+
+fn invalid<E: Into<String>>(message: E) -> io::Error {
+    io::Error::new(ErrorKind::InvalidData, message.into())
+}
+
+fn write_number(bytes: &mut Vec<u8>, mut number: u64) {
+    while number >= 0x80 {
+        bytes.push((number as u8 & 0x7f) | 0x80);
+        number >>= 7;
+    }
+    bytes.push(number as u8);
+}
+
+/// 64-bit FNV-1a, which catches truncated and corrupted files without pulling in a dependency
+fn checksum(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3))
+}
+
+/// Input walks through the bytes of a forest, failing once they run out
+struct Input<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Input<'a> {
+    fn take(&mut self, length: usize) -> io::Result<&'a [u8]> {
+        if length > self.bytes.len() {
+            return Err(invalid("Unexpected end of forest"));
+        }
+
+        let (taken, rest) = self.bytes.split_at(length);
+        self.bytes = rest;
+        Ok(taken)
+    }
+
+    fn number(&mut self) -> io::Result<u64> {
+        let mut number = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.take(1)?[0];
+            number |= u64::from(byte & 0x7f) << shift;
+
+            if byte & 0x80 == 0 {
+                return Ok(number);
+            }
+        }
+
+        Err(invalid("Number is too long"))
+    }
+
+    fn length(&mut self) -> io::Result<usize> {
+        let number = self.number()?;
+        if number > usize::MAX as u64 {
+            return Err(invalid(format!("Number is too large: {}", number)));
+        }
+
+        Ok(number as usize)
+    }
+}
+
+#[cfg(test)]
+mod binary_tests {
+    use std::io::ErrorKind;
+
+    use super::super::fixtures::product_of_groups;
+    use super::super::Forest;
+    use super::super::Manager;
+
+    fn write(forest: &Forest<String>) -> Vec<u8> {
+        let mut bytes = vec![];
+        forest.write_to(&mut bytes).unwrap();
+        bytes
+    }
+
+    #[test]
+    fn forests_read_back_what_was_written() {
+        let forest = Forest::many(&[
+            vec![String::from("1"), String::from("2")],
+            vec![String::from("2"), String::from("3")],
+            vec![String::from("4")],
+        ]);
+
+        let read: Forest<String> = Forest::read_from(&write(&forest)[..]).unwrap();
+
+        assert_eq!(forest, read);
+        assert_eq!(forest.occurrences(), read.occurrences());
+    }
+
+    #[test]
+    fn empty_forests_and_the_empty_set_read_back() {
+        let empty = Forest::<String>::empty();
+        let unit = Forest::<String>::choose(&[], 0);
+
+        assert_eq!(empty, Forest::read_from(&write(&empty)[..]).unwrap());
+        assert_eq!(unit, Forest::read_from(&write(&unit)[..]).unwrap());
+        assert_eq!(1, unit.count());
+    }
+
+    #[test]
+    fn ordered_forests_read_back_the_same_sets() {
+        let forest = Forest::many_with_order(&[
+            vec![String::from("1"), String::from("2")],
+            vec![String::from("3")],
+        ], &[String::from("3")]);
+
+        let read: Forest<String> = Forest::read_from(&write(&forest)[..]).unwrap();
+
+        assert_eq!(forest, read);
+        assert_eq!(vec![vec![String::from("1"), String::from("2")], vec![String::from("3")]], read.trees());
+    }
+
+    #[test]
+    fn forests_read_into_a_chosen_manager() {
+        let forest = Forest::many(&[
+            vec![String::from("1"), String::from("2")],
+            vec![String::from("2"), String::from("3")],
+            vec![String::from("4")],
+        ]);
+
+        let manager = Manager::new();
+
+        let read: Forest<String> = Forest::read_from_in(&write(&forest)[..], &manager).unwrap();
+
+        assert_eq!(&manager, read.manager());
+        assert_eq!(forest.trees(), read.trees());
+    }
+
+    #[test]
+    fn shared_nodes_keep_the_output_small() {
+        let forest = product_of_groups(30, 2);
+
+        let bytes = write(&forest);
+
+        assert_eq!(1 << 30, forest.count());
+        assert!(bytes.len() < 1_000, "{} bytes", bytes.len());
+        assert_eq!(forest, Forest::read_from(&bytes[..]).unwrap());
+    }
+
+    #[test]
+    fn corrupt_bytes_are_refused() {
+        let forest = Forest::many(&[
+            vec![String::from("1"), String::from("2")],
+            vec![String::from("2"), String::from("3")],
+            vec![String::from("4")],
+        ]);
+
+        let bytes = write(&forest);
+
+        let mut flipped = bytes.clone();
+        flipped[6] ^= 1;
+        let truncated = &bytes[..bytes.len() - 1];
+
+        for bytes in &[&flipped[..], truncated, b"ZDD"] {
+            let error = Forest::<String>::read_from(*bytes).unwrap_err();
+
+            assert_eq!(ErrorKind::InvalidData, error.kind());
+        }
+    }
+
+    #[test]
+    fn malformed_structure_is_refused_even_with_a_valid_checksum() {
+        let with_checksum = |mut bytes: Vec<u8>| {
+            let checksum = super::checksum(&bytes);
+            bytes.extend_from_slice(&checksum.to_le_bytes());
+            bytes
+        };
+
+        let invalid = vec![
+            (&b"ZDD2\x02\x00\x00\x00"[..], "Unsupported forest version"),
+            (&b"ZDD2\x01\x01\x01a\x01\x01\x05\x00\x01\x02"[..], "tests item 5"),
+            (&b"ZDD2\x01\x01\x01a\x01\x01\x00\x02\x01\x02"[..], "used before it is defined"),
+            (&b"ZDD2\x01\x01\x01a\x01\x01\x00\x00\x01\x03"[..], "Root 3 is not in the table"),
+            (&b"ZDD2\x01\x01\x01a\x01\x01\x00\x00\x01\x02\x00"[..], "Unexpected bytes"),
+            (&b"ZDD2\x01\x01\x01a\x01\x01\x00\x01\x00\x02"[..], "high edge to the empty family"),
+            (&b"ZDD2\x01\x01\x01a\x02\x01\x00\x00\x01\x02"[..], "does not occur as often"),
+        ];
+
+        for (bytes, message) in invalid {
+            let error = Forest::<String>::read_from(&with_checksum(bytes.to_vec())[..]).unwrap_err();
+
+            assert_eq!(ErrorKind::InvalidData, error.kind());
+            assert!(error.to_string().contains(message), "'{}' does not mention '{}'", error, message);
+        }
+
+        let valid = with_checksum(b"ZDD2\x01\x01\x01a\x01\x01\x00\x00\x01\x02".to_vec());
+        assert_eq!(
+            vec![vec![String::from("a")]],
+            Forest::<String>::read_from(&valid[..]).unwrap().trees()
+        );
+    }
+}
@@ -0,0 +1,129 @@
+use std::fmt;
+use std::hash::Hash;
+
+use hashbrown::HashMap;
+use hashbrown::HashSet;
+
+use dot::Graph;
+use dot::Path;
+
+use super::Forest;
+use super::Node;
+use super::NodeId;
+
+/// Writes every node reachable from the forest's root once, however many branches share it
+pub fn to_dot<T>(forest: &Forest<T>, highlight: Option<&[T]>) -> String
+    where T: Hash + Eq + Clone + Ord + Sync + Send + fmt::Display {
+    let _operation = forest.manager().operation();
+
+    let path = highlight
+        .map(|set| path(forest, set))
+        .unwrap_or_default();
+
+    let mut graph = Graph::new();
+    visit(forest, forest.root.id(), &path, &mut HashMap::new(), &mut graph);
+    graph.to_string()
+}
+
+/// Follows the high edge of every item in `set` and the low edge of every other item
+fn path<T>(forest: &Forest<T>, set: &[T]) -> Path<NodeId>
+    where T: Hash + Eq + Clone + Ord + Sync + Send {
+    let priorities: HashSet<_> = forest.universe.get_priorities(set);
+
+    let mut path = Path::default();
+    let mut id = forest.root.id();
+    while let Node::Branch(priority, low, high) = Node::from(id) {
+        let take_high = priorities.contains(&priority);
+        path.take(id, take_high);
+        id = if take_high { high } else { low };
+    }
+    path.visit(id);
+
+    path
+}
+
+fn visit<T>(forest: &Forest<T>, id: NodeId, path: &Path<NodeId>, names: &mut HashMap<NodeId, usize>, graph: &mut Graph) -> usize
+    where T: Hash + Eq + Clone + Ord + Sync + Send + fmt::Display {
+    if let Some(name) = names.get(&id) {
+        return *name;
+    }
+
+    let name = names.len();
+    names.insert(id, name);
+
+    match Node::from(id) {
+        Node::Branch(priority, low, high) => {
+            graph.branch(name, &forest.universe.get_item(priority).unwrap().to_string(), path.visits(id));
+
+            let low_name = visit(forest, low, path, names, graph);
+            let high_name = visit(forest, high, path, names, graph);
+            graph.edge(name, low_name, false, path.takes(id, false));
+            graph.edge(name, high_name, true, path.takes(id, true));
+        }
+        Node::Always => graph.leaf(name, "Always", path.visits(id)),
+        Node::Never => graph.leaf(name, "Never", path.visits(id)),
+    }
+
+    name
+}
+
+#[cfg(test)]
+mod dot_tests {
+    use super::super::fixtures::product_of_groups;
+    use super::super::Forest;
+
+    #[test]
+    fn to_dot_of_empty_forest() {
+        let forest: Forest<&str> = Forest::empty();
+
+        assert_eq!("digraph {\n    0 [label=\"Never\", shape=box];\n}", forest.to_dot());
+    }
+
+    #[test]
+    fn to_dot_labels_branches_with_items() {
+        let forest = Forest::many(&[vec!["a", "b"], vec!["b"]]);
+
+        assert_eq!(
+            "digraph {
+    0 [label=\"b\"];
+    1 [label=\"Never\", shape=box];
+    2 [label=\"a\"];
+    3 [label=\"Always\", shape=box];
+    2 -> 3 [style=dashed];
+    2 -> 3;
+    0 -> 1 [style=dashed];
+    0 -> 2;
+}",
+            forest.to_dot()
+        );
+    }
+
+    #[test]
+    fn to_dot_writes_shared_nodes_once() {
+        let forest = product_of_groups(12, 2);
+
+        let dot = forest.to_dot();
+
+        assert_eq!(forest.node_count() + 2, dot.matches("label=").count());
+        assert_eq!(2 * forest.node_count(), dot.matches("->").count());
+    }
+
+    #[test]
+    fn to_dot_highlighting_marks_the_path_of_a_set() {
+        let forest = Forest::many(&[vec!["a", "b"], vec!["b"]]);
+
+        assert_eq!(
+            "digraph {
+    0 [label=\"b\", color=red];
+    1 [label=\"Never\", shape=box];
+    2 [label=\"a\", color=red];
+    3 [label=\"Always\", shape=box, color=red];
+    2 -> 3 [style=dashed];
+    2 -> 3 [color=red, penwidth=2];
+    0 -> 1 [style=dashed];
+    0 -> 2 [color=red, penwidth=2];
+}",
+            forest.to_dot_highlighting(&["a", "b"])
+        );
+    }
+}
@@ -0,0 +1,151 @@
+use std::hash::{Hash, Hasher};
+
+use hashbrown::HashMap;
+
+use super::Forest;
+use super::Node;
+use super::NodeId;
+
+/// The Mersenne prime 2^61 - 1, which every fingerprint is reduced modulo
+const MODULUS: u64 = (1 << 61) - 1;
+
+/// Sums, over every member set, the product of a number drawn from each of its items' hashes
+///
+/// That sum only depends on which sets are members, so it comes out the same whatever the item order, the
+/// universe or the arena ids, and two different families agree with a chance of about one in 2^61 per item
+pub fn fingerprint<T>(forest: &Forest<T>) -> u64
+    where T: Hash + Eq + Clone + Ord + Sync + Send {
+    let _operation = forest.manager().operation();
+
+    let mut values = HashMap::new();
+    let mut sums = HashMap::new();
+    fingerprint_inner(forest, forest.root.id(), &mut values, &mut sums)
+}
+
+fn fingerprint_inner<T>(
+    forest: &Forest<T>,
+    id: NodeId,
+    values: &mut HashMap<NodeId, u64>,
+    sums: &mut HashMap<NodeId, u64>,
+) -> u64
+    where T: Hash + Eq + Clone + Ord + Sync + Send {
+    let (priority, low, high) = match Node::from(id) {
+        Node::Branch(priority, low, high) => (priority, low, high),
+        Node::Always => return 1,
+        Node::Never => return 0,
+    };
+
+    if let Some(sum) = sums.get(&id) {
+        return *sum;
+    }
+
+    let low = fingerprint_inner(forest, low, values, sums);
+    let high = fingerprint_inner(forest, high, values, sums);
+    let value = *values.entry(id)
+        .or_insert_with(|| value(forest.universe.get_item(priority).unwrap()));
+
+    let sum = (u128::from(low) + u128::from(value) * u128::from(high)) % u128::from(MODULUS);
+
+    sums.insert(id, sum as u64);
+    sum as u64
+}
+
+/// A nonzero number below the modulus, taken from the item's hash with a hasher that is the same in every process
+fn value<T: Hash>(item: &T) -> u64 {
+    let mut hasher = Fnv(0xcbf2_9ce4_8422_2325);
+    item.hash(&mut hasher);
+
+    hasher.finish() % (MODULUS - 1) + 1
+}
+
+/// 64-bit FNV-1a, which unlike `DefaultHasher` is not allowed to change between Rust releases
+struct Fnv(u64);
+
+impl Hasher for Fnv {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 = (self.0 ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+}
+
+#[cfg(test)]
+mod fingerprint_tests {
+    use super::super::Forest;
+    use super::super::Manager;
+
+    #[test]
+    fn fingerprint_ignores_how_a_forest_was_built() {
+        let forest = Forest::many(&[
+            vec!["1", "2"],
+            vec!["2", "3"],
+            vec!["4"],
+        ]);
+
+        let rebuilt = Forest::many(&[vec!["4"], vec!["1", "2"]])
+            .union(Forest::many(&[vec!["2", "3"], vec!["1", "5"]]))
+            .difference(Forest::unit(&["1", "5"]));
+
+        assert_eq!(forest.fingerprint(), rebuilt.fingerprint());
+        assert_eq!(forest.fingerprint(), forest.clone().sift().fingerprint());
+        assert_eq!(
+            forest.fingerprint(),
+            Forest::many_with_order(&forest.trees(), &["4", "3"]).fingerprint()
+        );
+        assert_eq!(
+            forest.fingerprint(),
+            Forest::many_in(&forest.trees(), &Manager::new()).fingerprint()
+        );
+    }
+
+    #[test]
+    fn fingerprint_tells_families_apart() {
+        let forest = Forest::many(&[
+            vec!["1", "2"],
+            vec!["2", "3"],
+            vec!["4"],
+        ]);
+
+        let fingerprints = [
+            Forest::<&str>::empty().fingerprint(),
+            Forest::choose(&["1"], 0).fingerprint(),
+            forest.fingerprint(),
+            Forest::many(&[vec!["1", "2"], vec!["2", "3"]]).fingerprint(),
+            Forest::many(&[vec!["1", "2"], vec!["3"], vec!["4"]]).fingerprint(),
+            Forest::many(&[vec!["1"], vec!["2"]]).fingerprint(),
+            Forest::unit(&["1", "2"]).fingerprint(),
+        ];
+
+        for (index, fingerprint) in fingerprints.iter().enumerate() {
+            assert!(!fingerprints[index + 1..].contains(fingerprint));
+        }
+    }
+
+    #[test]
+    fn same_family_ignores_universes_and_managers() {
+        let forest = Forest::many(&[
+            vec!["1", "2"],
+            vec!["2", "3"],
+            vec!["4"],
+        ]);
+
+        let rebuilt = Forest::many_in(&[vec!["4"], vec!["2", "3"], vec!["1", "2"]], &Manager::new())
+            .union(Forest::unit(&["5"]));
+
+        assert!(!forest.same_family(&rebuilt));
+        assert!(forest.same_family(&rebuilt.subset_not("5")));
+        assert!(forest.same_family(&Forest::many_with_order(&forest.trees(), &["4"])));
+        assert!(!forest.same_family(&Forest::many(&[vec!["1", "2"], vec!["2", "3"]])));
+    }
+
+    #[test]
+    fn fingerprint_is_stable() {
+        assert_eq!(0, Forest::<&str>::empty().fingerprint());
+        assert_eq!(1, Forest::<&str>::choose(&[], 0).fingerprint());
+        assert_eq!(679_922_959_887_335_899, Forest::unit(&["1", "2"]).fingerprint());
+    }
+}
@@ -0,0 +1,16 @@
+use super::Forest;
+
+/// Every set of `root` plus one of `choices` items from each of `groups` groups
+///
+/// That makes `choices^groups` members from only a few nodes per group, too many to ever enumerate
+pub fn product_of_groups(groups: usize, choices: usize) -> Forest<String> {
+    (0..groups)
+        .map(|group| {
+            let items: Vec<_> = (1..=choices)
+                .map(|choice| format!("{}-{}", group, choice))
+                .collect();
+
+            Forest::unique(&items)
+        })
+        .fold(Forest::unit(&[String::from("root")]), Forest::product)
+}
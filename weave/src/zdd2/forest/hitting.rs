@@ -0,0 +1,65 @@
+#[cfg(test)]
+mod tests {
+    use super::super::Forest;
+
+    #[test]
+    fn conflicts_are_resolved_by_removing_one_item_from_each() {
+        let conflicts = Forest::many(&[
+            vec!["a", "b"],
+            vec!["b", "c"],
+            vec!["c", "d"],
+        ]);
+
+        assert_eq!(
+            Forest::many(&[
+                vec!["a", "c"],
+                vec!["b", "c"],
+                vec!["b", "d"],
+            ]),
+            conflicts.minimal_hitting_sets()
+        );
+    }
+
+    #[test]
+    fn hitting_sets_of_hitting_sets_are_the_minimal_sets() {
+        let conflicts = Forest::many(&[
+            vec!["a", "b", "c"],
+            vec!["a", "d"],
+            vec!["b", "d", "e"],
+            vec!["a", "d", "e"],
+        ]);
+
+        assert_eq!(
+            conflicts.clone().minimal(),
+            conflicts.minimal_hitting_sets().minimal_hitting_sets()
+        );
+    }
+
+    #[test]
+    fn every_hitting_set_hits_every_conflict() {
+        let conflicts = Forest::many(&[
+            vec!["a", "b", "c"],
+            vec!["c", "d"],
+            vec!["a", "e"],
+            vec!["b", "e"],
+        ]);
+
+        let hitting_sets = conflicts.clone().minimal_hitting_sets();
+
+        assert!(!hitting_sets.is_empty());
+        for hitting_set in hitting_sets.trees() {
+            for conflict in conflicts.trees() {
+                assert!(conflict.iter().any(|item| hitting_set.contains(item)));
+            }
+        }
+        assert_eq!(hitting_sets.clone(), hitting_sets.minimal());
+    }
+
+    #[test]
+    fn empty_forest_is_hit_by_the_empty_set() {
+        assert_eq!(
+            vec![Vec::<&str>::new()],
+            Forest::<&str>::empty().minimal_hitting_sets().trees()
+        );
+    }
+}
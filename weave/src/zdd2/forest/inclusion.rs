@@ -0,0 +1,110 @@
+#[cfg(test)]
+mod tests {
+    use super::super::Forest;
+    use super::super::Manager;
+    use super::super::Universe;
+
+    #[test]
+    fn forest_is_a_subset_of_a_larger_one_over_other_items() {
+        let old_rules = Forest::many(&[
+            vec!["a", "b"],
+            vec!["b", "c"],
+            vec!["c"],
+        ]);
+
+        let new_rules = Forest::many(&[
+            vec!["b", "c"],
+            vec!["c"],
+        ]);
+
+        assert!(new_rules.is_subset_of(&old_rules));
+        assert!(old_rules.is_superset_of(&new_rules));
+        assert!(!old_rules.is_subset_of(&new_rules));
+        assert!(!new_rules.is_superset_of(&old_rules));
+    }
+
+    #[test]
+    fn forest_that_allows_something_new_is_not_a_subset() {
+        let old_rules = Forest::many(&[
+            vec!["a", "b"],
+            vec!["b", "c"],
+            vec!["c"],
+        ]);
+
+        let new_rules = Forest::many(&[
+            vec!["b", "c"],
+            vec!["d"],
+        ]);
+
+        assert!(!new_rules.is_subset_of(&old_rules));
+    }
+
+    #[test]
+    fn forests_are_subsets_of_themselves_and_supersets_of_empty() {
+        let old_rules = Forest::many(&[
+            vec!["a", "b"],
+            vec!["b", "c"],
+            vec!["c"],
+        ]);
+
+        let empty = Forest::empty();
+
+        assert!(old_rules.is_subset_of(&old_rules));
+        assert!(empty.is_subset_of(&old_rules));
+        assert!(old_rules.is_superset_of(&empty));
+        assert!(!old_rules.is_subset_of(&empty));
+    }
+
+    #[test]
+    fn disjoint_forests_share_no_member() {
+        let old_rules = Forest::many(&[
+            vec!["a", "b"],
+            vec!["b", "c"],
+            vec!["c"],
+        ]);
+
+        let others = Forest::many(&[
+            vec!["a"],
+            vec!["a", "b", "c"],
+        ]);
+
+        assert!(old_rules.is_disjoint_from(&others));
+        assert!(others.is_disjoint_from(&old_rules));
+        assert!(!old_rules.is_disjoint_from(&others.union(Forest::unit(&["c"]))));
+        assert!(old_rules.is_disjoint_from(&Forest::empty()));
+    }
+
+    #[test]
+    fn predicates_agree_with_the_algebra() {
+        let forest1 = Forest::many(&[
+            vec!["a", "b"],
+            vec!["b", "c"],
+            vec!["c"],
+        ]);
+        let forest2 = Forest::many(&[
+            vec!["a", "b"],
+            vec!["d"],
+        ]);
+
+        assert_eq!(
+            forest1.clone().difference(forest2.clone()).is_empty(),
+            forest1.is_subset_of(&forest2)
+        );
+        assert_eq!(
+            forest1.clone().intersect(forest2.clone()).is_empty(),
+            forest1.is_disjoint_from(&forest2)
+        );
+    }
+
+    #[test]
+    fn predicates_work_across_shared_universes_and_managers() {
+        let universe = Universe::from_items(&["a", "b", "c"]).share();
+        let manager = Manager::new();
+
+        let forest1 = Forest::many_shared(&[vec!["a", "b"]], &universe);
+        let forest2 = Forest::many_shared_in(&[vec!["a", "b"], vec!["c"]], &universe, &manager);
+
+        assert!(forest1.is_subset_of(&forest2));
+        assert!(!forest1.is_disjoint_from(&forest2));
+    }
+}
@@ -0,0 +1,57 @@
+#[cfg(test)]
+mod tests {
+    use super::super::Forest;
+
+    #[test]
+    fn maximal_of_empty_is_empty() {
+        assert_eq!(Forest::<&str>::empty(), Forest::empty().maximal());
+        assert_eq!(Forest::<&str>::empty(), Forest::empty().minimal());
+    }
+
+    #[test]
+    fn maximal_keeps_biggest_sets() {
+        let forest = Forest::many(&[
+            vec!["a", "b", "c"],
+            vec!["a", "b"],
+            vec!["b", "d"],
+            vec!["c"],
+            vec!["d"],
+        ]);
+
+        assert_eq!(
+            Forest::many(&[
+                vec!["a", "b", "c"],
+                vec!["b", "d"],
+            ]),
+            forest.maximal()
+        );
+    }
+
+    #[test]
+    fn minimal_keeps_smallest_sets() {
+        let forest = Forest::many(&[
+            vec!["a", "b", "c"],
+            vec!["a", "b"],
+            vec!["b", "d"],
+            vec!["c"],
+            vec!["d"],
+        ]);
+
+        assert_eq!(
+            Forest::many(&[
+                vec!["a", "b"],
+                vec!["c"],
+                vec!["d"],
+            ]),
+            forest.minimal()
+        );
+    }
+
+    #[test]
+    fn antichains_are_their_own_extremes() {
+        let forest = Forest::unique(&["a", "b", "c"]);
+
+        assert_eq!(forest.clone(), forest.clone().maximal());
+        assert_eq!(forest.clone(), forest.minimal());
+    }
+}
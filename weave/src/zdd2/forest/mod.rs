@@ -1,12 +1,31 @@
 use std::fmt;
 use std::hash::Hash;
+use std::io;
+use std::io::{Read, Write};
+use std::ops::Add;
+use std::ops::Bound;
+use std::ops::RangeBounds;
+use std::str::FromStr;
+use std::sync::Arc;
 
 use itertools::Itertools;
-
+use rand::Rng;
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+
+pub use self::node::CacheConfig;
+pub use self::node::collect;
+pub use self::node::Collection;
+pub use self::node::configure_cache;
+pub use self::node::Eviction;
+pub use self::node::Manager;
 use self::node::Node;
 use self::node::NodeId;
 use self::node::Priority;
-use self::universe::Universe;
+use self::node::Root;
+pub use self::trees::Trees;
+pub use self::universe::Universe;
+pub use self::universe::UniverseBuilder;
+use self::weight::Objective;
 
 #[macro_use]
 mod node;
@@ -20,17 +39,103 @@ mod intersect;
 mod subset;
 #[cfg(test)]
 mod product;
+#[cfg(test)]
+mod algebra;
+#[cfg(test)]
+mod restrict;
+#[cfg(test)]
+mod maximal;
+#[cfg(test)]
+mod hitting;
+#[cfg(test)]
+mod inclusion;
+#[cfg(test)]
+mod size;
+#[cfg(test)]
+mod reorder;
+#[cfg(test)]
+mod order;
+#[cfg(test)]
+mod shared;
+#[cfg(test)]
+mod fixtures;
+mod sample;
+mod rank;
+mod weight;
+mod dot;
+mod fingerprint;
+mod binary;
 
 /// Forest is an immutable set of sets
-#[derive(Clone, Eq, PartialEq, Serialize, Deserialize)]
+///
+/// Forests share their universe when they can, which lets operations between them skip translating either diagram
+#[derive(Clone, Deserialize)]
 pub struct Forest<T: Hash + Eq + Clone + Ord> {
-    root: NodeId,
-    universe: Universe<T>,
+    root: Root,
+    universe: Arc<Universe<T>>,
+}
+
+impl<T: Hash + Eq + Clone + Ord> Forest<T> {
+    /// This forest's diagram and universe in the default order, without any items that no set holds
+    ///
+    /// The diagram is copied into the manager of the running operation if it is bound to another one
+    fn default_order(&self) -> (Node, Universe<T>) {
+        let root = Manager::with_current(|manager| self.root.node_in(manager));
+
+        let universe = self.universe.reindex_by_occurrences(&root.occurrences());
+        let root = translate_root(&self.universe, &universe, root);
+
+        (root, universe)
+    }
+}
+
+/// Forests are equal when they hold the same sets, whatever order their items are in or manager they are bound to
+impl<T: Hash + Eq + Clone + Ord> PartialEq for Forest<T> {
+    fn eq(&self, other: &Self) -> bool {
+        let manager = self.root.manager();
+        if self.universe == other.universe && manager == other.root.manager() {
+            return self.root == other.root;
+        }
+        if !self.universe.is_ordered() && !other.universe.is_ordered() && self.universe != other.universe {
+            return false;
+        }
+
+        let _operation = manager.operation();
+
+        if self.universe == other.universe {
+            return Node::from(self.root.id()) == other.root.node_in(manager);
+        }
+
+        self.default_order() == other.default_order()
+    }
+}
+
+impl<T: Hash + Eq + Clone + Ord> Eq for Forest<T> {}
+
+/// The format only records occurrences, so forests with an explicit item order are written in the default one
+impl<T: Hash + Eq + Clone + Ord + Serialize> Serialize for Forest<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let _operation = self.root.manager().operation();
+
+        let mut state = serializer.serialize_struct("Forest", 2)?;
+        if self.universe.is_ordered() {
+            let (root, universe) = self.default_order();
+
+            state.serialize_field("root", &Root::new(root))?;
+            state.serialize_field("universe", &universe)?;
+        } else {
+            state.serialize_field("root", &self.root)?;
+            state.serialize_field("universe", &self.universe)?;
+        }
+        state.end()
+    }
 }
 
 impl<T: Hash + Eq + Clone + Ord + fmt::Debug> fmt::Debug for Forest<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "\n{}", self.fmt_inner(self.root.into(), 1))
+        let _operation = self.root.manager().operation();
+
+        write!(f, "\n{}", self.fmt_inner(self.root.id().into(), 1))
     }
 }
 
@@ -55,97 +160,494 @@ impl<T: Hash + Eq + Clone + Ord + fmt::Debug> Forest<T> {
 
 impl<T: Hash + Eq + Clone + Ord + Sync + Send> Forest<T> {
     pub fn empty() -> Self {
+        Forest::empty_in(Manager::global())
+    }
+
+    pub fn unit(items: &[T]) -> Self {
+        Forest::unit_in(items, Manager::global())
+    }
+
+    pub fn many(matrix: &[Vec<T>]) -> Self {
+        Forest::many_in(matrix, Manager::global())
+    }
+
+    pub fn unique(set: &[T]) -> Self {
+        Forest::unique_in(set, Manager::global())
+    }
+
+    pub fn many_shared(matrix: &[Vec<T>], universe: &Arc<Universe<T>>) -> Self {
+        Forest::many_shared_in(matrix, universe, Manager::global())
+    }
+
+    /// Like `many`, but with the items of `order` placed first, in that order, ahead of the rest
+    pub fn many_with_order(matrix: &[Vec<T>], order: &[T]) -> Self {
+        Forest::many_with_universe(matrix, &Universe::builder().order(order))
+    }
+
+    pub fn many_with_universe(matrix: &[Vec<T>], universe: &UniverseBuilder<T>) -> Self {
+        Forest::many_with_universe_in(matrix, universe, Manager::global())
+    }
+
+    pub fn choose(items: &[T], k: usize) -> Self {
+        Forest::choose_in(items, k, Manager::global())
+    }
+
+    pub fn empty_in(manager: &Manager) -> Self {
+        let _operation = manager.operation();
+
         let universe = Universe::default();
         let root = Node::NEVER;
 
-        Forest { root, universe }
+        Forest { root: Root::new(root), universe: Arc::new(universe) }
     }
 
-    pub fn unit(items: &[T]) -> Self {
+    pub fn unit_in(items: &[T], manager: &Manager) -> Self {
+        let _operation = manager.operation();
+
         let universe = Universe::from_items(items);
         let root = universe.get_priorities::<Node>(items);
 
-        Forest { root: root.into(), universe }
+        Forest { root: Root::new(root), universe: Arc::new(universe) }
     }
 
-    pub fn many(matrix: &[Vec<T>]) -> Self {
+    pub fn many_in(matrix: &[Vec<T>], manager: &Manager) -> Self {
+        let _operation = manager.operation();
+
         let universe = Universe::from_matrix(matrix);
 
         let root = matrix.iter()
             .map(|items| universe.get_priorities::<Node>(items))
             .fold(Node::Never, Node::union);
 
-        Forest { root: root.into(), universe }
+        Forest { root: Root::new(root), universe: Arc::new(universe) }
     }
 
-    pub fn unique(set: &[T]) -> Self {
+    /// Like `many_in`, but in the item order `universe` chooses, which later operations keep
+    pub fn many_with_universe_in(matrix: &[Vec<T>], universe: &UniverseBuilder<T>, manager: &Manager) -> Self {
+        let _operation = manager.operation();
+
+        let universe = universe.build(matrix);
+
+        let root = matrix.iter()
+            .map(|items| universe.get_priorities::<Node>(items))
+            .fold(Node::Never, Node::union);
+
+        Forest { root: Root::new(root), universe: Arc::new(universe) }
+    }
+
+    pub fn unique_in(set: &[T], manager: &Manager) -> Self {
+        let _operation = manager.operation();
+
         let universe = Universe::from_items(set);
 
         let root = universe.get_priorities::<Vec<_>>(set)
             .into_iter()
             .fold(Node::Never, |root, item| Node::branch(item, root, Node::Always));
 
-        Forest { root: root.into(), universe }
+        Forest { root: Root::new(root), universe: Arc::new(universe) }
     }
 
-    fn canonical(root: impl Into<NodeId>, universe: Universe<T>) -> Self {
-        let trees = trees::trees(root.into())
-            .into_iter()
-            .map(|set| universe.get_items::<Vec<_>>(&set))
-            .collect::<Vec<_>>();
+    /// Every set of exactly `k` distinct items, built without listing them one by one
+    pub fn choose_in(items: &[T], k: usize, manager: &Manager) -> Self {
+        let _operation = manager.operation();
+
+        let universe = Universe::from_items(items);
+        let root = Node::choose(&universe.get_priorities::<Vec<_>>(items), k);
+
+        Self::canonical(root, Arc::new(universe))
+    }
+
+    /// Like `many_in`, but over a shared universe, which forests built from the same catalog can hold in common
+    ///
+    /// Items missing from `universe` go after the rest in a copy that this forest holds instead
+    pub fn many_shared_in(matrix: &[Vec<T>], universe: &Arc<Universe<T>>, manager: &Manager) -> Self {
+        let _operation = manager.operation();
+
+        let missing: Vec<T> = matrix.iter()
+            .flatten()
+            .filter(|item| universe.get_priority(item).is_none())
+            .cloned()
+            .sorted()
+            .collect();
+        let universe = if missing.is_empty() {
+            universe.clone()
+        } else {
+            Arc::new(universe.extend(&missing))
+        };
+
+        let root = matrix.iter()
+            .map(|items| universe.get_priorities::<Node>(items))
+            .fold(Node::Never, Node::union);
+
+        Forest { root: Root::new(root), universe }
+    }
+
+    /// The manager this forest's nodes live in
+    pub fn manager(&self) -> &Manager {
+        self.root.manager()
+    }
 
-        Self::many(&trees)
+    /// The universe this forest's items come from, which `many_shared` can build other forests over
+    pub fn universe(&self) -> &Arc<Universe<T>> {
+        &self.universe
     }
 
+    /// Must be called while the operation that produced `root` still holds its guard
+    ///
+    /// A fixed universe is kept as it is, as is any other that would come out the same
+    fn canonical(root: impl Into<NodeId>, universe: Arc<Universe<T>>) -> Self {
+        let root = Node::from(root.into());
+        if universe.is_fixed() {
+            return Forest { root: Root::new(root), universe };
+        }
+
+        let canonical_universe = universe.reindex(&root.occurrences());
+        let (root, universe) = if canonical_universe == *universe {
+            (root, universe)
+        } else {
+            (translate_root(&universe, &canonical_universe, root), Arc::new(canonical_universe))
+        };
+
+        let forest = Forest { root: Root::new(root), universe };
+        if forest.manager().reorder_due() {
+            return forest.sift();
+        }
+
+        forest
+    }
+
+    /// Rewrites this forest under the item order that sifting finds smallest, and keeps that order through later operations
+    ///
+    /// The forest holds the same sets, so it still equals the one it was sifted from
+    pub fn sift(self) -> Self {
+        let _operation = self.root.manager().operation();
+
+        let (root, priorities) = Node::from(self.root.id()).sift(self.universe.len());
+        let universe = self.universe.permute(&priorities);
+
+        Forest { root: Root::new(root), universe: Arc::new(universe) }
+    }
+
+    /// The number of branches in this forest's diagram, which depends on the order of its items
+    pub fn node_count(&self) -> usize {
+        let _operation = self.manager().operation();
+
+        Node::from(self.root.id()).size()
+    }
+
+    /// The number of sets in the forest, saturating at `usize::MAX`
     pub fn len(&self) -> usize {
-        trees::trees(self.root).len()
+        self.count().min(usize::MAX as u128) as usize
+    }
+
+    /// The number of sets in the forest, saturating at `u128::MAX`
+    pub fn count(&self) -> u128 {
+        let _operation = self.manager().operation();
+
+        Node::from(self.root.id()).count()
     }
 
     pub fn is_empty(&self) -> bool {
-        trees::trees(self.root).is_empty()
+        self.root.id() == Node::NEVER
+    }
+
+    /// Whether `set` is a member, found by following a single path down the diagram
+    pub fn contains(&self, set: &[T]) -> bool {
+        let priorities: Option<Vec<Priority>> = set.iter()
+            .map(|item| self.universe.get_priority(item))
+            .collect();
+        let mut priorities = match priorities {
+            Some(priorities) => priorities.into_iter().sorted().dedup().peekable(),
+            None => return false,
+        };
+
+        let _operation = self.manager().operation();
+
+        let mut node = Node::from(self.root.id());
+        while let Node::Branch(priority, low, high) = node {
+            node = match priorities.peek() {
+                Some(next) if *next < priority => return false,
+                Some(next) if *next == priority => {
+                    priorities.next();
+                    Node::from(high)
+                }
+                _ => Node::from(low),
+            };
+        }
+
+        node == Node::Always && priorities.peek().is_none()
     }
 
     pub fn trees(&self) -> Vec<Vec<T>> {
-        trees::trees(self.root)
+        self.iter().collect()
+    }
+
+    /// Lazily yields each set in the same order as `trees`
+    pub fn iter(&self) -> Trees<'_, T> {
+        Trees::new(&self.root, &self.universe)
+    }
+
+    /// Picks a member set uniformly at random, or `None` if the forest is empty
+    pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Option<Vec<T>> {
+        sample::sample(self, rng)
+    }
+
+    /// Picks `n` distinct member sets uniformly at random, in the same order as `trees`
+    ///
+    /// Returns every set when the forest has no more than `n` of them
+    pub fn sample_n<R: Rng + ?Sized>(&self, rng: &mut R, n: usize) -> Vec<Vec<T>> {
+        sample::sample_n(self, rng, n)
+    }
+
+    /// The position of `set` among the members, in the same order as `trees`, or `None` if it is not one
+    pub fn rank(&self, set: &[T]) -> Option<u128> {
+        rank::rank(self, set)
+    }
+
+    /// The member set at position `index`, in the same order as `trees`, or `None` past the last one
+    pub fn unrank(&self, index: u128) -> Option<Vec<T>> {
+        rank::unrank(self, index)
+    }
+
+    /// The member set whose items weigh the least in total, along with that total
+    ///
+    /// The empty set weighs `W::default()`
+    pub fn min_weight<W, F>(&self, weight: F) -> Option<(Vec<T>, W)>
+        where W: Copy + PartialOrd + Add<Output=W> + Default, F: Fn(&T) -> W {
+        weight::best(self, weight, Objective::Minimize)
+    }
+
+    /// The member set whose items weigh the most in total, along with that total
+    pub fn max_weight<W, F>(&self, weight: F) -> Option<(Vec<T>, W)>
+        where W: Copy + PartialOrd + Add<Output=W> + Default, F: Fn(&T) -> W {
+        weight::best(self, weight, Objective::Maximize)
+    }
+
+    /// The `n` lightest member sets, lightest first
+    pub fn min_weight_n<W, F>(&self, n: usize, weight: F) -> Vec<(Vec<T>, W)>
+        where W: Copy + PartialOrd + Add<Output=W> + Default, F: Fn(&T) -> W {
+        weight::best_n(self, n, weight, Objective::Minimize)
+    }
+
+    /// The `n` heaviest member sets, heaviest first
+    pub fn max_weight_n<W, F>(&self, n: usize, weight: F) -> Vec<(Vec<T>, W)>
+        where W: Copy + PartialOrd + Add<Output=W> + Default, F: Fn(&T) -> W {
+        weight::best_n(self, n, weight, Objective::Maximize)
+    }
+
+    /// The diagram behind this forest in Graphviz DOT, with each node written once
+    pub fn to_dot(&self) -> String where T: fmt::Display {
+        dot::to_dot(self, None)
+    }
+
+    /// Like `to_dot`, with the edges that looking up `set` follows drawn in red
+    pub fn to_dot_highlighting(&self, set: &[T]) -> String where T: fmt::Display {
+        dot::to_dot(self, Some(set))
+    }
+
+    /// Writes this forest in a compact binary format, with the text of each item written once
+    pub fn write_to<W: Write>(&self, writer: W) -> io::Result<()> where T: fmt::Display {
+        binary::write_to(self, writer)
+    }
+
+    /// Reads a forest that `write_to` wrote, checking its checksum and structure
+    pub fn read_from<R: Read>(reader: R) -> io::Result<Self> where T: FromStr {
+        Forest::read_from_in(reader, Manager::global())
+    }
+
+    pub fn read_from_in<R: Read>(reader: R, manager: &Manager) -> io::Result<Self> where T: FromStr {
+        binary::read_from(reader, manager)
+    }
+
+    /// How many member sets hold each item, counted on the diagram and saturating at `usize::MAX`
+    pub fn occurrences(&self) -> Vec<(T, usize)> {
+        let _operation = self.manager().operation();
+
+        self.universe.item_occurrences(&Node::from(self.root.id()).occurrences())
             .into_iter()
-            .map(|set| self.universe.get_items::<Vec<_>>(&set))
+            .sorted_by(|(item1, _), (item2, _)| Ord::cmp(item1, item2))
             .collect()
     }
 
-    pub fn occurrences(&self) -> Vec<(T, usize)> {
-        self.universe.occurrences()
-            .clone()
+    /// Like `occurrences`, but with counts as large as `count` can be
+    pub fn marginals(&self) -> Vec<(T, u128)> {
+        let _operation = self.manager().operation();
+
+        Node::from(self.root.id()).marginals()
             .into_iter()
+            .map(|(priority, count)| (self.universe.get_item(priority).unwrap().clone(), count))
             .sorted_by(|(item1, _), (item2, _)| Ord::cmp(item1, item2))
             .collect()
     }
 
-    pub fn intersect(self, other: Self) -> Self {
-        let (universe, self_root, other_root) = translate_roots(
-            (&self.universe, self.root.into()),
-            (&other.universe, other.root.into()),
-        );
-        let root = Node::intersect(self_root, other_root);
+    /// The items of this forest in the order its diagram tests them, from the top down
+    pub fn order(&self) -> Vec<T> {
+        self.universe.items().to_vec()
+    }
 
-        Self::canonical(root, universe)
+    /// Whether this forest and `other` hold the same sets, whatever their universes or managers
+    pub fn same_family(&self, other: &Self) -> bool {
+        self.relation(other, |self_root, other_root| self_root == other_root)
+    }
+
+    /// A hash of the sets this forest holds, which stays the same across item orders, universes and processes
+    ///
+    /// Items must hash the same way wherever the fingerprint is compared
+    pub fn fingerprint(&self) -> u64 {
+        fingerprint::fingerprint(self)
+    }
+
+    /// Whether every member of this forest is also a member of `other`
+    pub fn is_subset_of(&self, other: &Self) -> bool {
+        self.relation(other, Node::is_subset)
+    }
+
+    /// Whether every member of `other` is also a member of this forest
+    pub fn is_superset_of(&self, other: &Self) -> bool {
+        other.is_subset_of(self)
+    }
+
+    /// Whether this forest and `other` have no member in common
+    pub fn is_disjoint_from(&self, other: &Self) -> bool {
+        self.relation(other, Node::is_disjoint)
+    }
+
+    pub fn intersect(self, other: Self) -> Self {
+        self.binary(other, Node::intersect)
     }
 
     pub fn union(self, other: Self) -> Self {
-        let (universe, self_root, other_root) = translate_roots(
-            (&self.universe, self.root.into()),
-            (&other.universe, other.root.into()),
+        self.binary(other, Node::union)
+    }
+
+    pub fn product(self, other: Self) -> Self {
+        self.binary(other, Node::product)
+    }
+
+    /// The sets of this forest that are not in `other`
+    pub fn difference(self, other: Self) -> Self {
+        self.binary(other, Node::difference)
+    }
+
+    /// The sets that are in exactly one of this forest and `other`
+    pub fn symmetric_difference(self, other: Self) -> Self {
+        self.binary(other, Node::symmetric_difference)
+    }
+
+    /// The largest family whose product with `divisor` stays within this forest, using disjoint unions only
+    ///
+    /// Dividing by the empty forest gives the empty forest
+    pub fn quotient(self, divisor: Self) -> Self {
+        self.binary(divisor, Node::quotient)
+    }
+
+    /// The sets of this forest that are left over once `divisor` times the quotient is taken out
+    pub fn remainder(self, divisor: Self) -> Self {
+        self.binary(divisor, Node::remainder)
+    }
+
+    /// Every pairwise intersection of a set in this forest with a set in `other`
+    pub fn meet(self, other: Self) -> Self {
+        self.binary(other, Node::meet)
+    }
+
+    /// Every pairwise union of a set in this forest with a set in `other` that shares no items with it
+    pub fn disjoint_product(self, other: Self) -> Self {
+        self.binary(other, Node::disjoint_product)
+    }
+
+    /// The sets of this forest that contain every item of at least one set in `other`
+    pub fn restrict(self, other: Self) -> Self {
+        self.binary(other, Node::restrict)
+    }
+
+    /// The sets of this forest that fit within at least one set in `other`
+    pub fn permit(self, other: Self) -> Self {
+        self.binary(other, Node::permit)
+    }
+
+    /// The sets of this forest that contain no set in `other`, the complement of `restrict`
+    pub fn nonsup(self, other: Self) -> Self {
+        self.binary(other, Node::nonsup)
+    }
+
+    /// The sets of this forest that fit within no set in `other`, the complement of `permit`
+    pub fn nonsub(self, other: Self) -> Self {
+        self.binary(other, Node::nonsub)
+    }
+
+    /// The sets of this forest that no other set in it contains
+    pub fn maximal(self) -> Self {
+        self.unary(Node::maximal)
+    }
+
+    /// The sets of this forest that contain no other set in it
+    pub fn minimal(self) -> Self {
+        self.unary(Node::minimal)
+    }
+
+    /// The smallest sets of items that share at least one item with every set in this forest
+    ///
+    /// An empty forest is hit by the empty set alone
+    pub fn minimal_hitting_sets(self) -> Self {
+        self.unary(Node::minimal_hitting_sets)
+    }
+
+    /// The sets of this forest whose number of items falls within `range`
+    pub fn with_size<R: RangeBounds<usize>>(self, range: R) -> Self {
+        let _operation = self.root.manager().operation();
+
+        let min = match range.start_bound() {
+            Bound::Included(&min) => min,
+            Bound::Excluded(&min) => min.saturating_add(1),
+            Bound::Unbounded => 0,
+        };
+        let items = self.universe.occurrences().len();
+        let max = match range.end_bound() {
+            Bound::Included(&max) => Some(max.min(items)),
+            Bound::Excluded(&max) => max.min(items + 1).checked_sub(1),
+            Bound::Unbounded => Some(items),
+        };
+
+        let root = match max {
+            Some(max) => Node::from(self.root.id()).with_size(min, max),
+            None => Node::Never,
+        };
+
+        Self::canonical(root, self.universe)
+    }
+
+    fn unary(self, func: fn(Node) -> Node) -> Self {
+        let _operation = self.root.manager().operation();
+
+        let root = func(self.root.id().into());
+
+        Self::canonical(root, self.universe)
+    }
+
+    /// Like `binary`, for questions about both diagrams that need no forest built from the answer
+    fn relation(&self, other: &Self, func: fn(Node, Node) -> bool) -> bool {
+        let manager = self.manager();
+        let _operation = manager.operation();
+
+        let (_, self_root, other_root) = translate_roots(
+            (&self.universe, self.root.node_in(manager)),
+            (&other.universe, other.root.node_in(manager)),
         );
-        let root = Node::union(self_root, other_root);
 
-        Self::canonical(root, universe)
+        func(self_root, other_root)
     }
 
-    pub fn product(self, other: Self) -> Self {
+    /// Applies `func` to both roots once they are translated into a merged universe in this forest's manager
+    fn binary(self, other: Self, func: fn(Node, Node) -> Node) -> Self {
+        let manager = self.manager();
+        let _operation = manager.operation();
+
         let (universe, self_root, other_root) = translate_roots(
-            (&self.universe, self.root.into()),
-            (&other.universe, other.root.into()),
+            (&self.universe, self.root.node_in(manager)),
+            (&other.universe, other.root.node_in(manager)),
         );
-        let root = Node::product(self_root, other_root);
+        let root = func(self_root, other_root);
 
         Self::canonical(root, universe)
     }
@@ -162,7 +664,7 @@ impl<T: Hash + Eq + Clone + Ord + Sync + Send> Forest<T> {
         subset::subset_many(
             self,
             elements,
-            &|_| Forest::empty(),
+            &|forest| Forest::empty_in(forest.manager()),
             Node::subset_all,
         )
     }
@@ -177,28 +679,34 @@ impl<T: Hash + Eq + Clone + Ord + Sync + Send> Forest<T> {
     }
 }
 
-fn translate_roots<T: Hash + Eq + Clone + Ord>((self_universe, self_root): (&Universe<T>, Node), (other_universe, other_root): (&Universe<T>, Node)) -> (Universe<T>, Node, Node) {
+/// Shared universes, and fixed universes where one extends the other, need no merging and no translation
+fn translate_roots<T: Hash + Eq + Clone + Ord>((self_universe, self_root): (&Arc<Universe<T>>, Node), (other_universe, other_root): (&Arc<Universe<T>>, Node)) -> (Arc<Universe<T>>, Node, Node) {
+    if Arc::ptr_eq(self_universe, other_universe) {
+        return (self_universe.clone(), self_root, other_root);
+    }
+    if self_universe.is_fixed() && other_universe.is_fixed() {
+        if self_universe.extends(other_universe) {
+            return (self_universe.clone(), self_root, other_root);
+        }
+        if other_universe.extends(self_universe) {
+            return (other_universe.clone(), self_root, other_root);
+        }
+    }
+
     let universe = Universe::merge(self_universe, other_universe);
 
     let self_root = translate_root(self_universe, &universe, self_root);
     let other_root = translate_root(other_universe, &universe, other_root);
 
-    (universe, self_root, other_root)
+    (Arc::new(universe), self_root, other_root)
 }
 
 fn translate_root<T: Hash + Eq + Clone + Ord>(old_universe: &Universe<T>, new_universe: &Universe<T>, root: Node) -> Node {
-    match root {
-        Node::Branch(id, low, high) => {
-            let low = translate_root(old_universe, new_universe, low.into());
-            let high = translate_root(old_universe, new_universe, high.into());
-
-            let item = old_universe.get_item(id).unwrap();
-            let id = new_universe.get_priority(item).unwrap();
-
-            Node::branch(id, low, high)
-        }
-        _ => root
+    if new_universe.extends(old_universe) {
+        return root;
     }
+
+    root.translate(&old_universe.translation(new_universe))
 }
 
 #[cfg(test)]
@@ -272,6 +780,13 @@ mod empty_forest_tests {
         assert_eq!(true, forest.is_empty());
     }
 
+    #[test]
+    fn empty_forest_has_count_0() {
+        let forest: Forest<&str> = Forest::empty();
+
+        assert_eq!(0, forest.count());
+    }
+
     #[test]
     fn empty_forest_into() {
         let forest: Forest<&str> = Forest::empty();
@@ -354,6 +869,42 @@ mod many_forest_tests {
         );
     }
 
+    #[test]
+    fn many_forest_contains_its_sets() {
+        let forest: Forest<&str> = Forest::many(&[
+            vec!["1", "2"],
+            vec!["2", "3"]
+        ]).union(Forest::choose(&["1"], 0));
+
+        assert!(forest.contains(&["1", "2"]));
+        assert!(forest.contains(&["3", "2", "3"]));
+        assert!(forest.contains(&[]));
+        assert!(!forest.contains(&["2"]));
+        assert!(!forest.contains(&["1", "2", "3"]));
+        assert!(!forest.contains(&["1", "3"]));
+        assert!(!forest.contains(&["1", "4"]));
+    }
+
+    #[test]
+    fn empty_forest_contains_nothing() {
+        let forest: Forest<&str> = Forest::empty();
+
+        assert!(!forest.contains(&[]));
+        assert!(!forest.contains(&["1"]));
+    }
+
+    #[test]
+    fn many_forest_occurrences_follow_its_members() {
+        let forest: Forest<&str> = Forest::many(&[
+            vec!["1", "2"],
+            vec!["2", "3"],
+            vec!["2"],
+        ]).difference(Forest::unit(&["2"]));
+
+        assert_eq!(vec![("1", 1), ("2", 2), ("3", 1)], forest.occurrences());
+        assert_eq!(vec![("1", 1), ("2", 2), ("3", 1)], forest.marginals());
+    }
+
     #[test]
     fn unique_forest_into() {
         let forest: Forest<&str> = Forest::unique(&["1", "2"]);
@@ -371,6 +922,7 @@ mod many_forest_tests {
 
 #[cfg(test)]
 mod random_tests {
+    use super::fixtures::product_of_groups;
     use super::Forest;
 
     #[test]
@@ -398,6 +950,15 @@ mod random_tests {
         );
     }
 
+    #[test]
+    fn count_of_product_exceeds_usize() {
+        let forest = product_of_groups(40, 4);
+
+        assert_eq!(4u128.pow(40), forest.count());
+        assert_eq!(usize::MAX, forest.len());
+        assert!(!forest.is_empty());
+    }
+
     #[test]
     fn product_of_three_forests_of_three() {
         let forest = Forest::unique(&["1-1", "1-2", "1-3"])
@@ -405,6 +966,7 @@ mod random_tests {
             .product(Forest::unique(&["3-1", "3-2", "3-3"]));
 
         assert_eq!(27, forest.len());
+        assert_eq!(27, forest.count());
 
         let expected = Forest::many(&[
             vec!["1-1", "2-1", "3-1"],
@@ -442,3 +1004,57 @@ mod random_tests {
         );
     }
 }
+
+#[cfg(test)]
+mod manager_forest_tests {
+    use super::Forest;
+    use super::Manager;
+
+    #[test]
+    fn forests_are_bound_to_the_manager_they_are_built_in() {
+        let manager = Manager::new();
+
+        let forest = Forest::unit_in(&["1", "2"], &manager);
+
+        assert_eq!(&manager, forest.manager());
+        assert_eq!(&manager, forest.clone().subset("3").manager());
+        assert_eq!(Forest::unit(&["1", "2"]), forest);
+        assert_eq!(forest, Forest::unit(&["1", "2"]));
+        assert_eq!(Forest::many_with_order(&[vec!["1", "2"]], &["2"]), forest);
+        assert_eq!(Manager::global(), Forest::unit(&["1", "2"]).manager());
+    }
+
+    #[test]
+    fn operations_stay_in_the_left_forest_manager() {
+        let manager1 = Manager::new();
+        let manager2 = Manager::new();
+
+        let forest1 = Forest::unit_in(&["1", "2"], &manager1);
+        let forest2 = Forest::many_in(&[vec!["2", "3"], vec!["3", "4"]], &manager2);
+        let nodes = manager2.node_count();
+
+        let forest = forest1.union(forest2);
+
+        assert_eq!(&manager1, forest.manager());
+        assert_eq!(nodes, manager2.node_count());
+        assert_eq!(
+            Forest::many_in(&[vec!["1", "2"], vec!["2", "3"], vec!["3", "4"]], &manager1),
+            forest
+        );
+    }
+
+    #[test]
+    fn dropping_forests_frees_nodes_on_collect() {
+        let manager = Manager::new();
+
+        {
+            let forest = Forest::unit_in(&["1", "2"], &manager)
+                .product(Forest::unique_in(&["3", "4", "5"], &manager));
+
+            assert_eq!(3, forest.len());
+        }
+
+        assert!(manager.collect().reclaimed > 0);
+        assert_eq!(2, manager.node_count());
+    }
+}
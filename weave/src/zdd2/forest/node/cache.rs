@@ -0,0 +1,254 @@
+use std::hash::BuildHasher;
+
+use hashbrown::hash_map::DefaultHashBuilder;
+use hashbrown::HashMap;
+
+use super::Node;
+use super::NodeId;
+use super::Priority;
+use super::Manager;
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum Operation {
+    Union,
+    Intersect,
+    Product,
+    Difference,
+    SymmetricDifference,
+    Quotient,
+    Meet,
+    DisjointProduct,
+    Restrict,
+    Permit,
+    Maximal,
+    Minimal,
+    MinimalHittingSets,
+    IsSubset,
+    IsDisjoint,
+    WithSize(usize, usize),
+    Subset(Priority),
+    SubsetNot(Priority),
+    Change(Priority),
+}
+
+impl Operation {
+    fn is_commutative(self) -> bool {
+        match self {
+            Operation::Union | Operation::Intersect | Operation::Product | Operation::SymmetricDifference => true,
+            Operation::Meet | Operation::DisjointProduct | Operation::IsDisjoint => true,
+            Operation::Difference | Operation::Quotient | Operation::Restrict | Operation::Permit => false,
+            Operation::IsSubset => false,
+            Operation::Maximal | Operation::Minimal | Operation::MinimalHittingSets => false,
+            Operation::WithSize(_, _) => false,
+            Operation::Subset(_) | Operation::SubsetNot(_) | Operation::Change(_) => false,
+        }
+    }
+}
+
+/// What the computed table does with a new entry once it is full
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum Eviction {
+    /// Drop every cached result and start over
+    Flush,
+    /// Replace whichever result shares a slot with the new one
+    Overwrite,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct CacheConfig {
+    pub capacity: usize,
+    pub eviction: Eviction,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        CacheConfig { capacity: 1 << 18, eviction: Eviction::Overwrite }
+    }
+}
+
+type Key = (Operation, NodeId, NodeId);
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+enum Entries {
+    Flush(HashMap<Key, NodeId>),
+    Overwrite(Vec<Option<(Key, NodeId)>>),
+}
+
+/// ComputedTable memoizes the results of node operations, keyed on the operation and its operands
+#[derive(Clone, Debug)]
+pub struct ComputedTable {
+    config: CacheConfig,
+    entries: Entries,
+    hasher: DefaultHashBuilder,
+}
+
+impl Default for ComputedTable {
+    fn default() -> Self {
+        ComputedTable::with_config(CacheConfig::default())
+    }
+}
+
+impl ComputedTable {
+    pub fn with_config(config: CacheConfig) -> Self {
+        let entries = match config.eviction {
+            Eviction::Flush => Entries::Flush(HashMap::new()),
+            Eviction::Overwrite => Entries::Overwrite(vec![None; config.capacity]),
+        };
+
+        ComputedTable { config, entries, hasher: DefaultHashBuilder::default() }
+    }
+
+    pub fn get(&self, key: &Key) -> Option<NodeId> {
+        match self.entries {
+            Entries::Flush(ref entries) => entries.get(key).cloned(),
+            Entries::Overwrite(ref slots) => match self.slot(key).and_then(|slot| slots[slot]) {
+                Some((slot_key, result)) if slot_key == *key => Some(result),
+                _ => None,
+            },
+        }
+    }
+
+    pub fn insert(&mut self, key: Key, result: NodeId) {
+        let slot = self.slot(&key);
+        let capacity = self.config.capacity;
+
+        match self.entries {
+            Entries::Flush(ref mut entries) => {
+                if capacity == 0 {
+                    return;
+                }
+                if entries.len() >= capacity && !entries.contains_key(&key) {
+                    entries.clear();
+                }
+                entries.insert(key, result);
+            }
+            Entries::Overwrite(ref mut slots) => {
+                if let Some(slot) = slot {
+                    slots[slot] = Some((key, result));
+                }
+            }
+        }
+    }
+
+    pub fn clear(&mut self) {
+        *self = ComputedTable::with_config(self.config);
+    }
+
+    #[cfg(test)]
+    pub fn len(&self) -> usize {
+        match self.entries {
+            Entries::Flush(ref entries) => entries.len(),
+            Entries::Overwrite(ref slots) => slots.iter().filter(|slot| slot.is_some()).count(),
+        }
+    }
+
+    fn slot(&self, key: &Key) -> Option<usize> {
+        if self.config.capacity == 0 {
+            return None;
+        }
+
+        Some(self.hasher.hash_one(key) as usize % self.config.capacity)
+    }
+}
+
+fn key(operation: Operation, node1: Node, node2: Node) -> Key {
+    let id1 = NodeId::from(node1);
+    let id2 = NodeId::from(node2);
+
+    if operation.is_commutative() && id2 < id1 {
+        (operation, id2, id1)
+    } else {
+        (operation, id1, id2)
+    }
+}
+
+/// Looks up the result of `operation` on the operands, computing and caching it on a miss
+pub fn memoize<F: FnOnce() -> Node>(operation: Operation, (node1, node2): (Node, Node), func: F) -> Node {
+    let key = key(operation, node1, node2);
+
+    let cached = Manager::with_current(|manager| manager.cache().read().unwrap().get(&key));
+    if let Some(result) = cached {
+        return Node::from(result);
+    }
+
+    let result = func();
+    let result_id = NodeId::from(result);
+    Manager::with_current(|manager| manager.cache().write().unwrap().insert(key, result_id));
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::Node;
+    use super::super::NodeId;
+    use super::super::Priority;
+    use super::CacheConfig;
+    use super::ComputedTable;
+    use super::Eviction;
+    use super::key;
+    use super::Operation;
+
+    #[test]
+    fn get_returns_inserted_result() {
+        let key = (Operation::Union, NodeId(2), NodeId(3));
+
+        let mut table = ComputedTable::default();
+        table.insert(key, NodeId(4));
+
+        assert_eq!(Some(NodeId(4)), table.get(&key));
+        assert_eq!(None, table.get(&(Operation::Intersect, NodeId(2), NodeId(3))));
+    }
+
+    #[test]
+    fn key_is_symmetric_for_commutative_operations() {
+        let node1 = node!(id: 0);
+        let node2 = node!(id: 1);
+
+        assert_eq!(
+            key(Operation::Union, node1, node2),
+            key(Operation::Union, node2, node1)
+        );
+        assert_ne!(
+            key(Operation::Subset(Priority(0)), node1, node2),
+            key(Operation::Subset(Priority(0)), node2, node1)
+        );
+    }
+
+    #[test]
+    fn flush_drops_everything_when_full() {
+        let config = CacheConfig { capacity: 2, eviction: Eviction::Flush };
+
+        let mut table = ComputedTable::with_config(config);
+        table.insert((Operation::Union, NodeId(2), NodeId(3)), NodeId(4));
+        table.insert((Operation::Union, NodeId(3), NodeId(4)), NodeId(5));
+        table.insert((Operation::Union, NodeId(4), NodeId(5)), NodeId(6));
+
+        assert_eq!(1, table.len());
+        assert_eq!(Some(NodeId(6)), table.get(&(Operation::Union, NodeId(4), NodeId(5))));
+    }
+
+    #[test]
+    fn overwrite_never_exceeds_capacity() {
+        let config = CacheConfig { capacity: 4, eviction: Eviction::Overwrite };
+
+        let mut table = ComputedTable::with_config(config);
+        for id in 0..32 {
+            table.insert((Operation::Product, NodeId(id), Node::NEVER), NodeId(id));
+        }
+
+        assert!(table.len() <= 4);
+    }
+
+    #[test]
+    fn zero_capacity_caches_nothing() {
+        let key = (Operation::Union, NodeId(2), NodeId(3));
+
+        for &eviction in &[Eviction::Flush, Eviction::Overwrite] {
+            let mut table = ComputedTable::with_config(CacheConfig { capacity: 0, eviction });
+            table.insert(key, NodeId(4));
+
+            assert_eq!(None, table.get(&key));
+        }
+    }
+}
@@ -0,0 +1,73 @@
+use super::cache::memoize;
+use super::cache::Operation;
+use super::Node;
+use super::Priority;
+
+/// Toggles `element` in every set of the family rooted at `root`
+pub fn change(root: Node, element: Priority) -> Node {
+    match root {
+        Node::Branch(id, low, high) if id == element => {
+            Node::branch(id, high, low)
+        }
+        Node::Branch(id, _, _) if id < element => {
+            memoize(Operation::Change(element), (root, Node::Never), || change_inner(root, element))
+        }
+        Node::Never => Node::Never,
+        _ => Node::branch(element, Node::NEVER, root),
+    }
+}
+
+fn change_inner(root: Node, element: Priority) -> Node {
+    match root {
+        Node::Branch(id, low, high) => {
+            let low = change(low.into(), element);
+            let high = change(high.into(), element);
+
+            Node::branch(id, low, high)
+        }
+        _ => unreachable!("Only branches above the element are memoized"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::Node;
+    use super::super::Priority;
+
+    #[test]
+    fn change_adds_missing_element() {
+        let root = node! {
+            id: 0,
+            low: node!(id: 2),
+            high: node!(Always)
+        };
+
+        let expected = node! {
+            id: 0,
+            low: node! {
+                id: 1,
+                low: node!(Never),
+                high: node!(id: 2)
+            },
+            high: node!(id: 1)
+        };
+
+        assert_eq!(expected, Node::change(root, Priority(1)));
+    }
+
+    #[test]
+    fn change_removes_present_element() {
+        let root = node! {
+            id: 0,
+            low: node!(Never),
+            high: node!(id: 1)
+        };
+
+        assert_eq!(node!(id: 1), Node::change(root, Priority(0)));
+    }
+
+    #[test]
+    fn change_of_never_is_never() {
+        assert_eq!(Node::Never, Node::change(Node::Never, Priority(0)));
+    }
+}
@@ -0,0 +1,78 @@
+use hashbrown::HashMap;
+
+use super::Node;
+use super::NodeId;
+
+/// Counts the sets in the family rooted at `root`, saturating at `u128::MAX`
+pub fn count(root: Node) -> u128 {
+    let mut counts = HashMap::new();
+    count_inner(root, &mut counts)
+}
+
+/// Counts the sets below every node reachable from `root`, leaves included
+pub fn counts(root: Node) -> HashMap<NodeId, u128> {
+    let mut counts = HashMap::new();
+    count_inner(root, &mut counts);
+
+    counts.insert(Node::NEVER, 0);
+    counts.insert(NodeId::from(Node::Always), 1);
+    counts
+}
+
+fn count_inner(root: Node, counts: &mut HashMap<NodeId, u128>) -> u128 {
+    let (low, high) = match root {
+        Node::Branch(_, low, high) => (low, high),
+        Node::Always => return 1,
+        Node::Never => return 0,
+    };
+
+    let id = NodeId::from(root);
+    if let Some(count) = counts.get(&id) {
+        return *count;
+    }
+
+    let low = count_inner(low.into(), counts);
+    let high = count_inner(high.into(), counts);
+    let count = low.saturating_add(high);
+
+    counts.insert(id, count);
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::Node;
+    use super::super::Priority;
+
+    #[test]
+    fn count_of_leaves() {
+        assert_eq!(0, Node::count(Node::Never));
+        assert_eq!(1, Node::count(Node::Always));
+    }
+
+    #[test]
+    fn count_of_shared_nodes() {
+        // {{0, 2}, {1, 2}, {2}}
+        let root = node! {
+            id: 0,
+            low: node! {
+                id: 1,
+                low: node!(id: 2),
+                high: node!(id: 2)
+            },
+            high: node!(id: 2)
+        };
+
+        assert_eq!(3, Node::count(root));
+    }
+
+    #[test]
+    fn count_of_power_set_is_exponential() {
+        let root = (0..100)
+            .rev()
+            .map(Priority)
+            .fold(Node::Always, |root, id| Node::branch(id, root, root));
+
+        assert_eq!(1u128 << 100, Node::count(root));
+    }
+}
@@ -0,0 +1,170 @@
+use super::cache::memoize;
+use super::cache::Operation;
+use super::Node;
+
+/// The sets of `node1` that are not in `node2`
+pub fn difference(node1: Node, node2: Node) -> Node {
+    if node1 == node2 {
+        return Node::Never;
+    }
+
+    match (node1, node2) {
+        (Node::Never, _) => Node::Never,
+        (_, Node::Never) => node1,
+
+        (Node::Always, Node::Always) => Node::Never,
+
+        _ => memoize(Operation::Difference, (node1, node2), || difference_inner(node1, node2)),
+    }
+}
+
+fn difference_inner(node1: Node, node2: Node) -> Node {
+    match (node1, node2) {
+        (Node::Always, Node::Branch(_, low_2, _)) =>
+            difference(node1, low_2.into()),
+        (Node::Branch(id_1, low_1, high_1), Node::Always) =>
+            Node::branch(id_1, difference(low_1.into(), node2), high_1),
+
+        (Node::Branch(id_1, low_1, high_1), Node::Branch(id_2, _, _)) if id_1 < id_2 =>
+            Node::branch(id_1, difference(low_1.into(), node2), high_1),
+        (Node::Branch(id_1, _, _), Node::Branch(id_2, low_2, _)) if id_1 > id_2 =>
+            difference(node1, low_2.into()),
+
+        (Node::Branch(id_1, low_1, high_1), Node::Branch(_, low_2, high_2)) => {
+            let low = difference(low_1.into(), low_2.into());
+            let high = difference(high_1.into(), high_2.into());
+
+            Node::branch(id_1, low, high)
+        }
+
+        _ => unreachable!("Terminal differences are resolved before memoization"),
+    }
+}
+
+/// The sets that are in exactly one of `node1` and `node2`
+pub fn symmetric_difference(node1: Node, node2: Node) -> Node {
+    if node1 == node2 {
+        return Node::Never;
+    }
+
+    match (node1, node2) {
+        (_, Node::Never) => node1,
+        (Node::Never, _) => node2,
+
+        _ => memoize(Operation::SymmetricDifference, (node1, node2), || symmetric_difference_inner(node1, node2)),
+    }
+}
+
+fn symmetric_difference_inner(node1: Node, node2: Node) -> Node {
+    let (id, low, high) = match (node1, node2) {
+        (Node::Branch(id, low, high), Node::Always) => {
+            let low = symmetric_difference(low.into(), node2);
+            let high = Node::from(high);
+
+            (id, low, high)
+        }
+        (Node::Always, Node::Branch(id, low, high)) => {
+            let low = symmetric_difference(node1, low.into());
+            let high = Node::from(high);
+
+            (id, low, high)
+        }
+
+        (Node::Branch(id_1, low_1, high_1), Node::Branch(id_2, _, _)) if id_1 < id_2 => {
+            let low = symmetric_difference(low_1.into(), node2);
+            let high = Node::from(high_1);
+
+            (id_1, low, high)
+        }
+        (Node::Branch(id_1, _, _), Node::Branch(id_2, low_2, high_2)) if id_1 > id_2 => {
+            let low = symmetric_difference(node1, low_2.into());
+            let high = Node::from(high_2);
+
+            (id_2, low, high)
+        }
+        (Node::Branch(id_1, low_1, high_1), Node::Branch(_, low_2, high_2)) => {
+            let low = symmetric_difference(low_1.into(), low_2.into());
+            let high = symmetric_difference(high_1.into(), high_2.into());
+
+            (id_1, low, high)
+        }
+
+        _ => unreachable!("Terminal symmetric differences are resolved before memoization"),
+    };
+
+    Node::branch(id, low, high)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::Node;
+
+    fn family() -> Node {
+        // {{0, 1}, {1}, {}}
+        node! {
+            id: 0,
+            low: node!(id: 1, low: node!(Always)),
+            high: node!(id: 1)
+        }
+    }
+
+    #[test]
+    fn difference_with_itself_is_never() {
+        assert_eq!(Node::Never, Node::difference(family(), family()));
+    }
+
+    #[test]
+    fn difference_removes_the_empty_set() {
+        let expected = node! {
+            id: 0,
+            low: node!(id: 1),
+            high: node!(id: 1)
+        };
+
+        assert_eq!(expected, Node::difference(family(), Node::Always));
+    }
+
+    #[test]
+    fn difference_removes_nested_sets() {
+        // {{0, 1}, {}}
+        let expected = node! {
+            id: 0,
+            low: node!(Always),
+            high: node!(id: 1)
+        };
+
+        assert_eq!(expected, Node::difference(family(), node!(id: 1)));
+    }
+
+    #[test]
+    fn difference_only_removes_sets_that_match_exactly() {
+        // {{1}} \ {{0, 1}} == {{1}}
+        let other = node! {
+            id: 0,
+            low: node!(Never),
+            high: node!(id: 1)
+        };
+
+        assert_eq!(node!(id: 1), Node::difference(node!(id: 1), other));
+        assert_eq!(Node::Always, Node::difference(Node::Always, node!(id: 1)));
+    }
+
+    #[test]
+    fn symmetric_difference_keeps_sets_in_exactly_one() {
+        // {{0, 1}, {1}, {}} ^ {{1}, {2}} == {{0, 1}, {2}, {}}
+        let other = node! {
+            id: 1,
+            low: node!(id: 2),
+            high: node!(Always)
+        };
+
+        let expected = node! {
+            id: 0,
+            low: node!(id: 2, low: node!(Always)),
+            high: node!(id: 1)
+        };
+
+        assert_eq!(expected, Node::symmetric_difference(family(), other));
+        assert_eq!(expected, Node::symmetric_difference(other, family()));
+    }
+}
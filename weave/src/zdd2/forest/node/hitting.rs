@@ -0,0 +1,83 @@
+use super::cache::memoize;
+use super::cache::Operation;
+use super::Node;
+use super::restrict::contains_empty;
+use super::restrict::nonsup;
+use super::union::union;
+
+/// The inclusion-minimal sets that share an element with every set of the family rooted at `root`
+///
+/// Nothing hits the empty set, and the empty set vacuously hits the empty family
+pub fn minimal_hitting_sets(root: Node) -> Node {
+    match root {
+        Node::Never => Node::Always,
+        _ if contains_empty(root) => Node::Never,
+
+        _ => memoize(Operation::MinimalHittingSets, (root, Node::Never), || minimal_hitting_sets_inner(root)),
+    }
+}
+
+/// Sets without the top element must hit every set once it is dropped, while sets with it only need to hit
+/// those that lack it, and are kept only if they would not already hit everything without it
+fn minimal_hitting_sets_inner(root: Node) -> Node {
+    match root {
+        Node::Branch(id, low, high) => {
+            let low = Node::from(low);
+
+            let without = minimal_hitting_sets(union(low, high.into()));
+            let with = nonsup(minimal_hitting_sets(low), without);
+
+            Node::branch(id, without, with)
+        }
+        _ => unreachable!("Only branches are memoized"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::Node;
+
+    #[test]
+    fn empty_family_is_hit_by_the_empty_set() {
+        assert_eq!(Node::Always, Node::minimal_hitting_sets(Node::Never));
+    }
+
+    #[test]
+    fn family_with_the_empty_set_is_never_hit() {
+        assert_eq!(Node::Never, Node::minimal_hitting_sets(Node::Always));
+        assert_eq!(Node::Never, Node::minimal_hitting_sets(node!(id: 0, low: node!(Always))));
+    }
+
+    #[test]
+    fn single_set_is_hit_by_each_of_its_elements() {
+        // {{0, 1}} is hit by {{0}, {1}}
+        let root = node!(id: 0, low: node!(Never), high: node!(id: 1));
+
+        let expected = node! {
+            id: 0,
+            low: node!(id: 1),
+            high: node!(Always)
+        };
+
+        assert_eq!(expected, Node::minimal_hitting_sets(root));
+    }
+
+    #[test]
+    fn disjoint_sets_are_hit_by_one_element_of_each() {
+        // {{0}, {1}} is hit by {{0, 1}}
+        let root = node!(id: 0, low: node!(id: 1), high: node!(Always));
+
+        assert_eq!(
+            node!(id: 0, low: node!(Never), high: node!(id: 1)),
+            Node::minimal_hitting_sets(root)
+        );
+    }
+
+    #[test]
+    fn supersets_do_not_change_the_hitting_sets() {
+        // {{0}, {0, 1}} is hit by {{0}}
+        let root = node!(id: 0, low: node!(Never), high: node!(id: 1, low: node!(Always)));
+
+        assert_eq!(node!(id: 0), Node::minimal_hitting_sets(root));
+    }
+}
@@ -0,0 +1,112 @@
+use super::cache::memoize;
+use super::cache::Operation;
+use super::Node;
+use super::restrict::contains_empty;
+use super::subset::cofactors;
+use super::top;
+
+/// Whether every set in `node1` is also in `node2`, stopping at the first set that is not
+///
+/// Answers are cached as `Always` or `Never` so that they share the computed table with every other operation
+pub fn is_subset(node1: Node, node2: Node) -> bool {
+    match (node1, node2) {
+        (Node::Never, _) => true,
+        (_, Node::Never) => false,
+        _ if node1 == node2 => true,
+        (Node::Always, _) => contains_empty(node2),
+        (_, Node::Always) => false,
+
+        _ => memoize(Operation::IsSubset, (node1, node2), || answer(is_subset_inner(node1, node2))) == Node::Always,
+    }
+}
+
+fn is_subset_inner(node1: Node, node2: Node) -> bool {
+    let id = top(node1, node2);
+
+    let (low_1, high_1) = cofactors(node1, id);
+    let (low_2, high_2) = cofactors(node2, id);
+
+    is_subset(high_1, high_2) && is_subset(low_1, low_2)
+}
+
+/// Whether `node1` and `node2` have no set in common, stopping at the first shared one
+pub fn is_disjoint(node1: Node, node2: Node) -> bool {
+    match (node1, node2) {
+        (Node::Never, _) | (_, Node::Never) => true,
+        _ if node1 == node2 => false,
+        (Node::Always, _) => !contains_empty(node2),
+        (_, Node::Always) => !contains_empty(node1),
+
+        _ => memoize(Operation::IsDisjoint, (node1, node2), || answer(is_disjoint_inner(node1, node2))) == Node::Always,
+    }
+}
+
+fn is_disjoint_inner(node1: Node, node2: Node) -> bool {
+    let id = top(node1, node2);
+
+    let (low_1, high_1) = cofactors(node1, id);
+    let (low_2, high_2) = cofactors(node2, id);
+
+    is_disjoint(high_1, high_2) && is_disjoint(low_1, low_2)
+}
+
+fn answer(answer: bool) -> Node {
+    if answer { Node::Always } else { Node::Never }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::Node;
+
+    #[test]
+    fn never_is_a_subset_of_everything() {
+        assert!(Node::Never.is_subset(Node::Never));
+        assert!(Node::Never.is_subset(Node::Always));
+        assert!(Node::Never.is_subset(node!(id: 0)));
+        assert!(!node!(id: 0).is_subset(Node::Never));
+    }
+
+    #[test]
+    fn always_is_a_subset_of_families_with_the_empty_set() {
+        assert!(Node::Always.is_subset(node!(id: 0, low: node!(Always))));
+        assert!(!Node::Always.is_subset(node!(id: 0)));
+        assert!(!node!(id: 0).is_subset(Node::Always));
+    }
+
+    #[test]
+    fn subset_of_larger_family() {
+        // {{0, 1}} is a subset of {{0, 1}, {1}}, but not the other way around
+        let family1 = node!(id: 0, low: node!(Never), high: node!(id: 1));
+        let family2 = node!(id: 0, low: node!(id: 1), high: node!(id: 1));
+
+        assert!(family1.is_subset(family2));
+        assert!(!family2.is_subset(family1));
+    }
+
+    #[test]
+    fn sets_with_an_untested_element_are_not_a_subset() {
+        // {{0}, {1}} is not a subset of {{1}}
+        let family = node!(id: 0, low: node!(id: 1));
+
+        assert!(!family.is_subset(node!(id: 1)));
+        assert!(node!(id: 1).is_subset(family));
+    }
+
+    #[test]
+    fn disjoint_families() {
+        // {{0}} and {{1}} share no set, while {{0}, {1}} shares one with each
+        let family = node!(id: 0, low: node!(id: 1));
+
+        assert!(node!(id: 0).is_disjoint(node!(id: 1)));
+        assert!(!family.is_disjoint(node!(id: 0)));
+        assert!(!node!(id: 1).is_disjoint(family));
+    }
+
+    #[test]
+    fn disjoint_with_leaves() {
+        assert!(Node::Never.is_disjoint(Node::Never));
+        assert!(!Node::Always.is_disjoint(Node::Always));
+        assert!(Node::Always.is_disjoint(node!(id: 0)));
+        assert!(!node!(id: 0, low: node!(Always)).is_disjoint(Node::Always));
+    }
+}
@@ -1,3 +1,5 @@
+use super::cache::memoize;
+use super::cache::Operation;
 use super::Node;
 
 pub fn intersect(node1: Node, node2: Node) -> Node {
@@ -6,12 +8,20 @@ pub fn intersect(node1: Node, node2: Node) -> Node {
     }
 
     match (node1, node2) {
-        (_, Node::Always) => node1,
-        (Node::Always, _) => node2,
-
         (_, Node::Never) => Node::Never,
         (Node::Never, _) => Node::Never,
 
+        _ => memoize(Operation::Intersect, (node1, node2), || intersect_inner(node1, node2)),
+    }
+}
+
+fn intersect_inner(node1: Node, node2: Node) -> Node {
+    match (node1, node2) {
+        (Node::Branch(_, low_1, _), Node::Always) =>
+            intersect(low_1.into(), node2),
+        (Node::Always, Node::Branch(_, low_2, _)) =>
+            intersect(node1, low_2.into()),
+
         (Node::Branch(id_1, low_1, _), Node::Branch(id_2, _, _)) if id_1 < id_2 =>
             intersect(low_1.into(), node2),
 
@@ -24,5 +34,7 @@ pub fn intersect(node1: Node, node2: Node) -> Node {
 
             Node::branch(id_1, low, high)
         }
+
+        _ => unreachable!("Terminal intersections are resolved before memoization"),
     }
 }
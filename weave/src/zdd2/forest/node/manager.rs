@@ -0,0 +1,271 @@
+use std::cell::RefCell;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::RwLock;
+use std::sync::RwLockReadGuard;
+
+use hashbrown::HashMap;
+
+use super::arena::Collection;
+use super::arena::NodeArena;
+use super::cache::CacheConfig;
+use super::cache::ComputedTable;
+use super::Node;
+use super::NodeId;
+
+lazy_static! {
+    static ref GLOBAL: Manager = Manager::new();
+}
+
+thread_local! {
+    static CURRENT: RefCell<Vec<Manager>> = const { RefCell::new(Vec::new()) };
+}
+
+struct Store {
+    arena: RwLock<NodeArena>,
+    cache: RwLock<ComputedTable>,
+    operations: RwLock<()>,
+    /// The node count that triggers the next automatic reorder, or zero when reordering is manual
+    reorder_threshold: AtomicUsize,
+}
+
+/// Manager owns a node arena and the computed table shared by operations on its nodes
+///
+/// Forests bound to different managers never contend on the same locks, and a manager's nodes are freed
+/// wholesale once the manager and every forest bound to it are dropped. Handles are cheap to clone.
+#[derive(Clone)]
+pub struct Manager(Arc<Store>);
+
+impl Default for Manager {
+    fn default() -> Self {
+        Manager::with_cache(CacheConfig::default())
+    }
+}
+
+impl Manager {
+    pub fn new() -> Self {
+        Manager::default()
+    }
+
+    pub fn with_cache(config: CacheConfig) -> Self {
+        Manager(Arc::new(Store {
+            arena: RwLock::new(NodeArena::new()),
+            cache: RwLock::new(ComputedTable::with_config(config)),
+            operations: RwLock::new(()),
+            reorder_threshold: AtomicUsize::new(0),
+        }))
+    }
+
+    /// The manager used by forests built without one
+    pub fn global() -> &'static Manager {
+        &GLOBAL
+    }
+
+    /// Replaces the computed table, dropping any cached results
+    pub fn configure_cache(&self, config: CacheConfig) {
+        *self.0.cache.write().unwrap() = ComputedTable::with_config(config);
+    }
+
+    /// Frees every node that is not reachable from a live forest bound to this manager
+    ///
+    /// Waits for running operations to finish, so it must not be called from inside one
+    pub fn collect(&self) -> Collection {
+        let _operations = self.0.operations.write().unwrap();
+
+        self.0.cache.write().unwrap().clear();
+        self.0.arena.write().unwrap().collect()
+    }
+
+    /// The number of nodes currently stored, including both leaves
+    pub fn node_count(&self) -> usize {
+        self.0.arena.read().unwrap().len()
+    }
+
+    /// Sifts every forest an operation produces once this manager holds `threshold` nodes, or never with `None`
+    ///
+    /// Each automatic reorder doubles the threshold, so that the nodes sifting leaves behind do not set off another
+    pub fn auto_reorder(&self, threshold: Option<usize>) {
+        self.0.reorder_threshold.store(threshold.unwrap_or(0), Ordering::SeqCst);
+    }
+
+    /// Whether the arena has grown past the threshold, in which case the threshold moves up
+    pub(crate) fn reorder_due(&self) -> bool {
+        let threshold = self.0.reorder_threshold.load(Ordering::SeqCst);
+        let node_count = self.node_count();
+        if threshold == 0 || node_count < threshold {
+            return false;
+        }
+
+        let next = node_count.saturating_mul(2);
+        self.0.reorder_threshold.compare_exchange(threshold, next, Ordering::SeqCst, Ordering::SeqCst).is_ok()
+    }
+
+    /// Makes this the manager used by node operations on the current thread until the returned guard is dropped
+    ///
+    /// Guards nest, so only the outermost guard for a manager on a thread waits for a running collection
+    pub(crate) fn operation(&self) -> OperationGuard<'_> {
+        let nested = CURRENT.with(|current| {
+            let mut current = current.borrow_mut();
+            let nested = current.contains(self);
+            current.push(self.clone());
+            nested
+        });
+
+        let operations = if nested { None } else { Some(self.0.operations.read().unwrap()) };
+
+        OperationGuard { _operations: operations }
+    }
+
+    /// Runs `func` against the manager of the innermost running operation, or the global manager outside of one
+    pub(crate) fn with_current<R, F: FnOnce(&Manager) -> R>(func: F) -> R {
+        CURRENT.with(|current| {
+            match current.borrow().last() {
+                Some(manager) => func(manager),
+                None => func(&GLOBAL),
+            }
+        })
+    }
+
+    pub(crate) fn add(&self, node: Node) -> NodeId {
+        let node_id = self.0.arena.read().unwrap().get_by_node(&node).cloned();
+
+        node_id.unwrap_or_else(|| self.0.arena.write().unwrap().add(node))
+    }
+
+    pub(crate) fn get(&self, node_id: NodeId) -> Node {
+        *self.0.arena.read().unwrap()
+            .get_by_id(node_id)
+            .unwrap_or_else(|| panic!("Expected node to exist for: {:?}", node_id.0))
+    }
+
+    pub(crate) fn retain(&self, root: NodeId) {
+        self.0.arena.write().unwrap().retain(root);
+    }
+
+    pub(crate) fn release(&self, root: NodeId) {
+        if let Ok(mut arena) = self.0.arena.write() {
+            arena.release(root);
+        }
+    }
+
+    pub(crate) fn cache(&self) -> &RwLock<ComputedTable> {
+        &self.0.cache
+    }
+
+    /// Copies the diagram below `root` out of `source` and into this manager
+    ///
+    /// Must be called while an operation on this manager is running
+    pub(crate) fn import(&self, source: &Manager, root: NodeId) -> Node {
+        if self == source {
+            return self.get(root);
+        }
+
+        import(source, root, &mut HashMap::new())
+    }
+}
+
+fn import(source: &Manager, node_id: NodeId, imported: &mut HashMap<NodeId, Node>) -> Node {
+    if let Some(node) = imported.get(&node_id) {
+        return *node;
+    }
+
+    let node = match source.get(node_id) {
+        Node::Branch(id, low, high) => {
+            let low = import(source, low, imported);
+            let high = import(source, high, imported);
+
+            Node::Branch(id, low.into(), high.into())
+        }
+        leaf => leaf,
+    };
+
+    imported.insert(node_id, node);
+    node
+}
+
+impl PartialEq for Manager {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for Manager {}
+
+impl Hash for Manager {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (&*self.0 as *const Store).hash(state)
+    }
+}
+
+impl fmt::Debug for Manager {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Manager({:p})", &*self.0)
+    }
+}
+
+/// OperationGuard holds off `collect` while an operation works with nodes that are not yet rooted
+pub struct OperationGuard<'a> {
+    _operations: Option<RwLockReadGuard<'a, ()>>,
+}
+
+impl<'a> Drop for OperationGuard<'a> {
+    fn drop(&mut self) {
+        CURRENT.with(|current| current.borrow_mut().pop());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Manager;
+    use super::super::NodeId;
+
+    #[test]
+    fn managers_store_nodes_separately() {
+        let manager1 = Manager::new();
+        let manager2 = Manager::new();
+
+        {
+            let _operation = manager1.operation();
+            let _root = NodeId::from(node!(id: 3, low: node!(Always), high: node!(id: 4)));
+        }
+
+        assert_eq!(4, manager1.node_count());
+        assert_eq!(2, manager2.node_count());
+    }
+
+    #[test]
+    fn nested_operations_restore_the_outer_manager() {
+        let manager1 = Manager::new();
+        let manager2 = Manager::new();
+
+        let _operation1 = manager1.operation();
+        {
+            let _operation2 = manager2.operation();
+            let _nested = manager1.operation();
+
+            Manager::with_current(|current| assert_eq!(&manager1, current));
+        }
+
+        Manager::with_current(|current| assert_eq!(&manager1, current));
+    }
+
+    #[test]
+    fn import_copies_a_diagram_between_managers() {
+        let source = Manager::new();
+        let target = Manager::new();
+
+        let root = {
+            let _operation = source.operation();
+            NodeId::from(node!(id: 0, low: node!(id: 1), high: node!(id: 1)))
+        };
+
+        let _operation = target.operation();
+        let imported = NodeId::from(target.import(&source, root));
+
+        assert_eq!(node!(id: 0, low: node!(id: 1), high: node!(id: 1)), imported.into());
+        assert_eq!(4, target.node_count());
+    }
+}
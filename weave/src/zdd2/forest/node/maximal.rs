@@ -0,0 +1,99 @@
+use super::cache::memoize;
+use super::cache::Operation;
+use super::Node;
+use super::restrict::nonsub;
+use super::restrict::nonsup;
+
+/// The sets of the family rooted at `root` that no other set contains
+pub fn maximal(root: Node) -> Node {
+    match root {
+        Node::Branch(..) => memoize(Operation::Maximal, (root, Node::Never), || maximal_inner(root)),
+        _ => root,
+    }
+}
+
+/// Sets without the top element are dropped when a set with it contains them
+fn maximal_inner(root: Node) -> Node {
+    match root {
+        Node::Branch(id, low, high) => {
+            let high = maximal(high.into());
+            let low = nonsub(maximal(low.into()), high);
+
+            Node::branch(id, low, high)
+        }
+        _ => unreachable!("Only branches are memoized"),
+    }
+}
+
+/// The sets of the family rooted at `root` that contain no other set
+pub fn minimal(root: Node) -> Node {
+    match root {
+        Node::Branch(..) => memoize(Operation::Minimal, (root, Node::Never), || minimal_inner(root)),
+        _ => root,
+    }
+}
+
+/// Sets with the top element are dropped when they contain a set without it
+fn minimal_inner(root: Node) -> Node {
+    match root {
+        Node::Branch(id, low, high) => {
+            let low = minimal(low.into());
+            let high = nonsup(minimal(high.into()), low);
+
+            Node::branch(id, low, high)
+        }
+        _ => unreachable!("Only branches are memoized"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::Node;
+
+    fn family() -> Node {
+        // {{0, 1}, {0}, {1, 2}, {2}}
+        node! {
+            id: 0,
+            low: node!(id: 1, low: node!(id: 2), high: node!(id: 2)),
+            high: node!(id: 1, low: node!(Always), high: node!(Always))
+        }
+    }
+
+    #[test]
+    fn maximal_drops_contained_sets() {
+        // {{0, 1}, {1, 2}}
+        let expected = node! {
+            id: 0,
+            low: node!(id: 1, low: node!(Never), high: node!(id: 2)),
+            high: node!(id: 1)
+        };
+
+        assert_eq!(expected, Node::maximal(family()));
+    }
+
+    #[test]
+    fn minimal_drops_containing_sets() {
+        // {{0}, {2}}
+        let expected = node! {
+            id: 0,
+            low: node!(id: 2),
+            high: node!(Always)
+        };
+
+        assert_eq!(expected, Node::minimal(family()));
+    }
+
+    #[test]
+    fn leaves_are_their_own_extremes() {
+        assert_eq!(Node::Always, Node::maximal(Node::Always));
+        assert_eq!(Node::Never, Node::minimal(Node::Never));
+    }
+
+    #[test]
+    fn the_empty_set_is_only_maximal_alone() {
+        let root = node!(id: 0, low: node!(Always));
+
+        assert_eq!(node!(id: 0), Node::maximal(root));
+        assert_eq!(Node::Always, Node::minimal(root));
+    }
+}
@@ -0,0 +1,148 @@
+use super::cache::memoize;
+use super::cache::Operation;
+use super::Node;
+use super::subset::cofactors;
+use super::top;
+use super::union::union;
+
+/// Every pairwise intersection of a set in `node1` with a set in `node2`
+pub fn meet(node1: Node, node2: Node) -> Node {
+    match (node1, node2) {
+        (_, Node::Never) | (Node::Never, _) => Node::Never,
+        (_, Node::Always) | (Node::Always, _) => Node::Always,
+
+        _ => memoize(Operation::Meet, (node1, node2), || meet_inner(node1, node2)),
+    }
+}
+
+fn meet_inner(node1: Node, node2: Node) -> Node {
+    let id = top(node1, node2);
+
+    let (low_1, high_1) = cofactors(node1, id);
+    let (low_2, high_2) = cofactors(node2, id);
+
+    let high = meet(high_1, high_2);
+    let low = union(
+        meet(low_1, low_2),
+        union(meet(low_1, high_2), meet(high_1, low_2)),
+    );
+
+    Node::branch(id, low, high)
+}
+
+/// Every pairwise union of a set in `node1` with a set in `node2` that shares no elements with it
+pub fn disjoint_product(node1: Node, node2: Node) -> Node {
+    match (node1, node2) {
+        (_, Node::Never) | (Node::Never, _) => Node::Never,
+        (_, Node::Always) => node1,
+        (Node::Always, _) => node2,
+
+        _ => memoize(Operation::DisjointProduct, (node1, node2), || disjoint_product_inner(node1, node2)),
+    }
+}
+
+fn disjoint_product_inner(node1: Node, node2: Node) -> Node {
+    let id = top(node1, node2);
+
+    let (low_1, high_1) = cofactors(node1, id);
+    let (low_2, high_2) = cofactors(node2, id);
+
+    let low = disjoint_product(low_1, low_2);
+    let high = union(disjoint_product(high_1, low_2), disjoint_product(low_1, high_2));
+
+    Node::branch(id, low, high)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::Node;
+
+    #[test]
+    fn meet_intersects_every_pair() {
+        // {{0, 1}, {2}} meet {{1, 2}} == {{1}, {2}}
+        let family1 = node! {
+            id: 0,
+            low: node!(id: 2),
+            high: node!(id: 1)
+        };
+        let family2 = node! {
+            id: 1,
+            low: node!(Never),
+            high: node!(id: 2)
+        };
+
+        let expected = node! {
+            id: 1,
+            low: node!(id: 2),
+            high: node!(Always)
+        };
+
+        assert_eq!(expected, Node::meet(family1, family2));
+        assert_eq!(expected, Node::meet(family2, family1));
+    }
+
+    #[test]
+    fn meet_of_disjoint_sets_is_always() {
+        assert_eq!(Node::Always, Node::meet(node!(id: 0), node!(id: 1)));
+    }
+
+    #[test]
+    fn meet_with_itself_keeps_common_subsets() {
+        // {{0}, {1}} meet itself == {{0}, {1}, {}}
+        let family = node! {
+            id: 0,
+            low: node!(id: 1),
+            high: node!(Always)
+        };
+
+        let expected = node! {
+            id: 0,
+            low: node!(id: 1, low: node!(Always)),
+            high: node!(Always)
+        };
+
+        assert_eq!(expected, Node::meet(family, family));
+    }
+
+    #[test]
+    fn disjoint_product_skips_overlapping_pairs() {
+        // {{0}, {1}} disjoint product {{1}, {2}} == {{0, 1}, {0, 2}, {1, 2}}
+        let family1 = node! {
+            id: 0,
+            low: node!(id: 1),
+            high: node!(Always)
+        };
+        let family2 = node! {
+            id: 1,
+            low: node!(id: 2),
+            high: node!(Always)
+        };
+
+        let expected = node! {
+            id: 0,
+            low: node!(id: 1, low: node!(Never), high: node!(id: 2)),
+            high: node!(id: 1, low: node!(id: 2), high: node!(Always))
+        };
+
+        assert_eq!(expected, Node::disjoint_product(family1, family2));
+        assert_eq!(expected, Node::disjoint_product(family2, family1));
+    }
+
+    #[test]
+    fn disjoint_product_with_itself_drops_repeats() {
+        // {{0}, {1}} disjoint product itself == {{0, 1}}
+        let family = node! {
+            id: 0,
+            low: node!(id: 1),
+            high: node!(Always)
+        };
+
+        let expected = node! {
+            id: 0,
+            low: node!(Never),
+            high: node!(id: 1)
+        };
+
+        assert_eq!(expected, Node::disjoint_product(family, family));
+    }
+}
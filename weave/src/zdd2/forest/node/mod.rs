@@ -1,20 +1,41 @@
 use std::fmt;
 use std::iter::FromIterator;
-use std::sync::RwLock;
 
-use self::arena::*;
+use hashbrown::HashMap;
+
+pub use self::arena::Collection;
+pub use self::cache::CacheConfig;
+pub use self::cache::Eviction;
+pub use self::manager::Manager;
+pub use self::root::Root;
 
 mod arena;
 
 #[macro_use]
 mod macros;
+mod cache;
+mod manager;
 mod parser;
 mod serialize;
+mod root;
 
 mod intersect;
 mod union;
 mod product;
+mod difference;
+mod quotient;
+mod meet;
+mod restrict;
+mod maximal;
+mod hitting;
+mod inclusion;
+mod size;
+mod reorder;
 mod subset;
+mod change;
+mod translate;
+mod occurrences;
+mod count;
 
 #[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
 pub struct Priority(pub(crate) usize);
@@ -107,6 +128,86 @@ impl Node {
         product::product(self, other)
     }
 
+    pub fn difference(self, other: Self) -> Self {
+        difference::difference(self, other)
+    }
+
+    pub fn symmetric_difference(self, other: Self) -> Self {
+        difference::symmetric_difference(self, other)
+    }
+
+    pub fn quotient(self, divisor: Self) -> Self {
+        quotient::quotient(self, divisor)
+    }
+
+    pub fn remainder(self, divisor: Self) -> Self {
+        quotient::remainder(self, divisor)
+    }
+
+    pub fn meet(self, other: Self) -> Self {
+        meet::meet(self, other)
+    }
+
+    pub fn disjoint_product(self, other: Self) -> Self {
+        meet::disjoint_product(self, other)
+    }
+
+    pub fn is_subset(self, other: Self) -> bool {
+        inclusion::is_subset(self, other)
+    }
+
+    pub fn is_disjoint(self, other: Self) -> bool {
+        inclusion::is_disjoint(self, other)
+    }
+
+    pub fn restrict(self, other: Self) -> Self {
+        restrict::restrict(self, other)
+    }
+
+    pub fn permit(self, other: Self) -> Self {
+        restrict::permit(self, other)
+    }
+
+    pub fn nonsup(self, other: Self) -> Self {
+        restrict::nonsup(self, other)
+    }
+
+    pub fn nonsub(self, other: Self) -> Self {
+        restrict::nonsub(self, other)
+    }
+
+    pub fn maximal(self) -> Self {
+        maximal::maximal(self)
+    }
+
+    pub fn minimal(self) -> Self {
+        maximal::minimal(self)
+    }
+
+    pub fn minimal_hitting_sets(self) -> Self {
+        hitting::minimal_hitting_sets(self)
+    }
+
+    pub fn with_size(self, min: usize, max: usize) -> Self {
+        size::with_size(self, min, max)
+    }
+
+    pub fn choose(elements: &[Priority], k: usize) -> Self {
+        size::choose(elements, k)
+    }
+
+    pub fn size(self) -> usize {
+        reorder::size(self)
+    }
+
+    pub fn sift(self, levels: usize) -> (Self, HashMap<Priority, Priority>) {
+        reorder::sift(self, levels)
+    }
+
+    pub fn swap(self, level: usize) -> Self {
+        reorder::swap(self, level)
+    }
+
     pub fn subset(self, element: Priority) -> Self {
         subset::subset(self, element)
     }
@@ -122,18 +223,44 @@ impl Node {
     pub fn subset_none(self, elements: &[Priority]) -> Self {
         subset::subset_none(self, elements)
     }
+
+    pub fn change(self, element: Priority) -> Self {
+        change::change(self, element)
+    }
+
+    pub fn translate(self, priorities: &HashMap<Priority, Priority>) -> Self {
+        translate::translate(self, priorities)
+    }
+
+    pub fn count(self) -> u128 {
+        count::count(self)
+    }
+
+    pub fn counts(self) -> HashMap<NodeId, u128> {
+        count::counts(self)
+    }
+
+    pub fn occurrences(self) -> HashMap<Priority, usize> {
+        occurrences::occurrences(self)
+    }
+
+    pub fn marginals(self) -> HashMap<Priority, u128> {
+        occurrences::marginals(self)
+    }
 }
 
-impl<'a> From<Node> for NodeId {
-    fn from(node: Node) -> Self {
-        let node_id = {
-            let guard = ARENA.read().unwrap();
-            guard.get_by_node(&node).cloned()
-        };
+/// The priority nearest the root among two operands, at least one of them a branch
+fn top(node1: Node, node2: Node) -> Priority {
+    match (node1, node2) {
+        (Node::Branch(id_1, _, _), Node::Branch(id_2, _, _)) => id_1.min(id_2),
+        (Node::Branch(id, _, _), _) | (_, Node::Branch(id, _, _)) => id,
+        _ => unreachable!("Terminal operands are resolved before memoization"),
+    }
+}
 
-        node_id.unwrap_or_else(
-            || ARENA.write().unwrap().add(node)
-        )
+impl From<Node> for NodeId {
+    fn from(node: Node) -> Self {
+        Manager::with_current(|manager| manager.add(node))
     }
 }
 
@@ -145,9 +272,7 @@ impl<'a> From<&'a Node> for NodeId {
 
 impl From<NodeId> for Node {
     fn from(node_id: NodeId) -> Self {
-        *ARENA.read().unwrap()
-            .get_by_id(node_id)
-            .unwrap_or_else(|| panic!("Expected node to exist for: {:?}", node_id))
+        Manager::with_current(|manager| manager.get(node_id))
     }
 }
 
@@ -163,11 +288,16 @@ impl<'a> From<&'a Node> for Node {
     }
 }
 
-lazy_static! {
-    pub static ref ARENA: RwLock<NodeArena> = {
-        let a = NodeArena::new();
-        RwLock::new(a)
-    };
+/// Replaces the computed table of the global manager, dropping any cached results
+pub fn configure_cache(config: CacheConfig) {
+    Manager::global().configure_cache(config);
+}
+
+/// Frees every node in the global manager that is not reachable from a live forest
+///
+/// Waits for running operations to finish, so it must not be called from inside one
+pub fn collect() -> Collection {
+    Manager::global().collect()
 }
 
 impl FromIterator<Priority> for Node {
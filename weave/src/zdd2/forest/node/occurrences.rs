@@ -0,0 +1,114 @@
+use hashbrown::HashMap;
+
+use super::count::counts;
+use super::Node;
+use super::NodeId;
+use super::Priority;
+
+/// Counts, for every priority in the family rooted at `root`, how many sets contain it, saturating at `usize::MAX`
+pub fn occurrences(root: Node) -> HashMap<Priority, usize> {
+    marginals(root).into_iter()
+        .map(|(priority, count)| (priority, count.min(usize::MAX as u128) as usize))
+        .collect()
+}
+
+/// Like `occurrences`, saturating at `u128::MAX` instead
+///
+/// Paths from the root are counted top down and sets below each node bottom up, so that every branch adds the
+/// sets that pass through its high edge: one for each path into it times each set below its high child
+pub fn marginals(root: Node) -> HashMap<Priority, u128> {
+    let branches = branches(root);
+    let counts = counts(root);
+
+    let mut paths: HashMap<NodeId, u128> = HashMap::new();
+    paths.insert(NodeId::from(root), 1);
+
+    let mut marginals: HashMap<Priority, u128> = HashMap::new();
+    for &(priority, id, low, high) in branches.iter() {
+        let path_count = paths[&id];
+
+        for child in &[low, high] {
+            let paths = paths.entry(*child).or_insert(0);
+            *paths = paths.saturating_add(path_count);
+        }
+
+        let marginal = marginals.entry(priority).or_insert(0);
+        *marginal = marginal.saturating_add(path_count.saturating_mul(counts[&high]));
+    }
+
+    marginals
+}
+
+/// Every branch reachable from `root`, ordered so that parents come before their children
+fn branches(root: Node) -> Vec<(Priority, NodeId, NodeId, NodeId)> {
+    let mut branches = HashMap::new();
+
+    let mut queue = vec![root];
+    while let Some(node) = queue.pop() {
+        if let Node::Branch(priority, low, high) = node {
+            let id = NodeId::from(node);
+            if branches.contains_key(&id) {
+                continue;
+            }
+
+            branches.insert(id, (priority, id, low, high));
+            queue.push(low.into());
+            queue.push(high.into());
+        }
+    }
+
+    let mut branches: Vec<_> = branches.into_iter()
+        .map(|(_, branch)| branch)
+        .collect();
+    branches.sort();
+    branches
+}
+
+#[cfg(test)]
+mod tests {
+    use hashbrown::HashMap;
+
+    use super::super::Node;
+    use super::super::Priority;
+
+    #[test]
+    fn occurrences_of_leaves_are_empty() {
+        assert_eq!(HashMap::new(), Node::occurrences(Node::Never));
+        assert_eq!(HashMap::new(), Node::occurrences(Node::Always));
+    }
+
+    #[test]
+    fn occurrences_count_shared_nodes_once_per_path() {
+        // {{0, 2}, {1, 2}, {2}}
+        let root = node! {
+            id: 0,
+            low: node! {
+                id: 1,
+                low: node!(id: 2),
+                high: node!(id: 2)
+            },
+            high: node!(id: 2)
+        };
+
+        let expected: HashMap<Priority, usize> = hashmap! {
+            Priority(0) => 1,
+            Priority(1) => 1,
+            Priority(2) => 3,
+        }.into_iter().collect();
+
+        assert_eq!(expected, Node::occurrences(root));
+    }
+
+    #[test]
+    fn marginals_do_not_saturate_at_usize() {
+        // Every subset of {0, ..., 69}, so each element is in 2^69 of them
+        let root = (0..70).rev()
+            .fold(Node::Always, |root, id| Node::branch(Priority(id), root, root));
+
+        let marginals = Node::marginals(root);
+
+        assert_eq!(70, marginals.len());
+        assert!(marginals.values().all(|count| *count == 1 << 69));
+        assert_eq!(Some(&usize::MAX), Node::occurrences(root).get(&Priority(0)));
+    }
+}
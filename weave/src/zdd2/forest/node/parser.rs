@@ -9,19 +9,6 @@ use super::Priority;
 const ALWAYS_NODE: &str = "(A)";
 const NEVER_NODE: &str = "(N)";
 
-pub fn build_node_string(node: impl Into<Node>) -> String {
-    match node.into() {
-        Node::Always => String::from(ALWAYS_NODE),
-        Node::Never => String::from(NEVER_NODE),
-        Node::Branch(id, low, high) => format!(
-            "({:?} {} {})",
-            id.0,
-            build_node_string(low),
-            build_node_string(high)
-        ),
-    }
-}
-
 pub fn parse_node_string(node_str: &str) -> Result<Node, Err<&[u8], u32>> {
     node(node_str.trim().as_bytes())
         .map(|(_, node)| node)
@@ -1,17 +1,21 @@
+use super::cache::memoize;
+use super::cache::Operation;
 use super::Node;
 
 pub fn product(node1: Node, node2: Node) -> Node {
-    if node1 == node2 {
-        return node1;
-    }
+    match (node1, node2) {
+        (_, Node::Always) => node1,
+        (Node::Always, _) => node2,
 
-    let (id, low, high) = match (node1, node2) {
-        (_, Node::Always) => return node1,
-        (Node::Always, _) => return node2,
+        (_, Node::Never) => Node::Never,
+        (Node::Never, _) => Node::Never,
 
-        (_, Node::Never) => return Node::Never,
-        (Node::Never, _) => return Node::Never,
+        _ => memoize(Operation::Product, (node1, node2), || product_inner(node1, node2)),
+    }
+}
 
+fn product_inner(node1: Node, node2: Node) -> Node {
+    let (id, low, high) = match (node1, node2) {
         (Node::Branch(id_1, low_1, high_1), Node::Branch(id_2, _, _)) if id_1 < id_2 => {
             let low = product(low_1.into(), node2);
             let high = product(high_1.into(), node2);
@@ -39,7 +43,9 @@ pub fn product(node1: Node, node2: Node) -> Node {
 
             (id_1, low_1_low_2, high)
         }
+
+        _ => unreachable!("Terminal products are resolved before memoization"),
     };
 
     Node::branch(id, low, high)
-}
\ No newline at end of file
+}
@@ -0,0 +1,130 @@
+use super::cache::memoize;
+use super::cache::Operation;
+use super::difference::difference;
+use super::intersect::intersect;
+use super::Node;
+use super::product::product;
+use super::subset::cofactors;
+
+/// The largest family `Q` whose product with `divisor` stays within `dividend`, using disjoint unions only
+///
+/// Dividing by the empty family gives the empty family
+pub fn quotient(dividend: Node, divisor: Node) -> Node {
+    match (dividend, divisor) {
+        (_, Node::Never) => Node::Never,
+        _ if dividend == divisor => Node::Always,
+        (_, Node::Always) => dividend,
+        (Node::Never, _) | (Node::Always, _) => Node::Never,
+
+        _ => memoize(Operation::Quotient, (dividend, divisor), || quotient_inner(dividend, divisor)),
+    }
+}
+
+fn quotient_inner(dividend: Node, divisor: Node) -> Node {
+    let (id, divisor_low, divisor_high) = match divisor {
+        Node::Branch(id, low, high) => (id, Node::from(low), Node::from(high)),
+        _ => unreachable!("Terminal quotients are resolved before memoization"),
+    };
+
+    let (dividend_low, dividend_high) = cofactors(dividend, id);
+
+    let quotient_high = quotient(dividend_high, divisor_high);
+    match (quotient_high, divisor_low) {
+        (Node::Never, _) | (_, Node::Never) => quotient_high,
+        _ => intersect(quotient_high, quotient(dividend_low, divisor_low)),
+    }
+}
+
+/// The sets of `dividend` that are left over after taking out `divisor` times their quotient
+pub fn remainder(dividend: Node, divisor: Node) -> Node {
+    let quotient = quotient(dividend, divisor);
+
+    difference(dividend, product(divisor, quotient))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::Node;
+
+    fn family() -> Node {
+        // {{0, 1}, {0, 2}, {1}, {3}}
+        node! {
+            id: 0,
+            low: node! {
+                id: 1,
+                low: node!(id: 3),
+                high: node!(Always)
+            },
+            high: node! {
+                id: 1,
+                low: node!(id: 2),
+                high: node!(Always)
+            }
+        }
+    }
+
+    #[test]
+    fn quotient_by_always_is_identity() {
+        assert_eq!(family(), Node::quotient(family(), Node::Always));
+    }
+
+    #[test]
+    fn quotient_by_itself_is_always() {
+        assert_eq!(Node::Always, Node::quotient(family(), family()));
+    }
+
+    #[test]
+    fn quotient_by_never_is_never() {
+        assert_eq!(Node::Never, Node::quotient(family(), Node::Never));
+    }
+
+    #[test]
+    fn quotient_by_single_element() {
+        // {{1}, {2}}
+        let expected = node! {
+            id: 1,
+            low: node!(id: 2),
+            high: node!(Always)
+        };
+
+        assert_eq!(expected, Node::quotient(family(), node!(id: 0)));
+    }
+
+    #[test]
+    fn quotient_by_family_keeps_common_cofactors() {
+        // {{0, 1}, {0, 2}, {1}, {3}} / {{0}, {}} == {{1}}
+        let divisor = node! {
+            id: 0,
+            low: node!(Always),
+            high: node!(Always)
+        };
+
+        assert_eq!(node!(id: 1), Node::quotient(family(), divisor));
+    }
+
+    #[test]
+    fn remainder_removes_the_divisible_part() {
+        // {{0, 1}, {0, 2}, {1}, {3}} % {{0}} == {{1}, {3}}
+        let expected = node! {
+            id: 1,
+            low: node!(id: 3),
+            high: node!(Always)
+        };
+
+        assert_eq!(expected, Node::remainder(family(), node!(id: 0)));
+    }
+
+    #[test]
+    fn quotient_and_remainder_rebuild_the_dividend() {
+        let divisor = node! {
+            id: 0,
+            low: node!(Always),
+            high: node!(Always)
+        };
+
+        let quotient = Node::quotient(family(), divisor);
+        let remainder = Node::remainder(family(), divisor);
+
+        assert_eq!(family(), Node::union(Node::product(divisor, quotient), remainder));
+    }
+}
@@ -0,0 +1,204 @@
+use hashbrown::HashMap;
+use hashbrown::HashSet;
+
+use super::Node;
+use super::NodeId;
+use super::Priority;
+
+/// Sifting gives up on a direction once the diagram grows past this factor of the best size seen
+const MAX_GROWTH: f64 = 1.2;
+
+/// The number of branches reachable from `root`
+pub fn size(root: Node) -> usize {
+    widths(root).values().sum()
+}
+
+/// The number of branches reachable from `root` at each priority
+fn widths(root: Node) -> HashMap<Priority, usize> {
+    let mut visited = HashSet::new();
+    let mut widths = HashMap::new();
+
+    let mut queue = vec![root];
+    while let Some(node) = queue.pop() {
+        if let Node::Branch(priority, low, high) = node {
+            if visited.insert(NodeId::from(node)) {
+                *widths.entry(priority).or_insert(0) += 1;
+                queue.push(low.into());
+                queue.push(high.into());
+            }
+        }
+    }
+
+    widths
+}
+
+/// Moves each priority in turn, widest level first, to wherever the diagram rooted at `root` is smallest
+///
+/// Priorities are positions in the order, so `levels` must exceed every priority in the diagram. Returns the
+/// rebuilt diagram along with the new priority given to each old one.
+pub fn sift(root: Node, levels: usize) -> (Node, HashMap<Priority, Priority>) {
+    let mut labels: Vec<Priority> = (0..levels).map(Priority).collect();
+
+    let widths = widths(root);
+    let mut variables: Vec<Priority> = widths.keys().cloned().collect();
+    variables.sort_by(|variable1, variable2| widths[variable2].cmp(&widths[variable1]).then(variable1.cmp(variable2)));
+
+    let mut root = root;
+    let mut best_size = size(root);
+    for variable in variables {
+        let start = labels.iter().position(|label| *label == variable).unwrap();
+        let mut position = start;
+        let mut best_position = start;
+
+        while position + 1 < levels {
+            root = swap(root, position);
+            labels.swap(position, position + 1);
+            position += 1;
+
+            if !track(size(root), position, &mut best_size, &mut best_position) {
+                break;
+            }
+        }
+        while position > 0 {
+            root = swap(root, position - 1);
+            labels.swap(position - 1, position);
+            position -= 1;
+
+            if !track(size(root), position, &mut best_size, &mut best_position) && position < start {
+                break;
+            }
+        }
+        while position < best_position {
+            root = swap(root, position);
+            labels.swap(position, position + 1);
+            position += 1;
+        }
+        while position > best_position {
+            root = swap(root, position - 1);
+            labels.swap(position - 1, position);
+            position -= 1;
+        }
+    }
+
+    let priorities = labels.into_iter()
+        .enumerate()
+        .map(|(position, label)| (label, Priority(position)))
+        .collect();
+
+    (root, priorities)
+}
+
+/// Remembers `position` if it is the best so far, and tells whether sifting should keep going
+fn track(size: usize, position: usize, best_size: &mut usize, best_position: &mut usize) -> bool {
+    if size < *best_size {
+        *best_size = size;
+        *best_position = position;
+    }
+
+    (size as f64) <= (*best_size as f64) * MAX_GROWTH
+}
+
+/// Exchanges the priorities at `level` and the level below it, rebuilding only the branches above them
+pub fn swap(root: Node, level: usize) -> Node {
+    let mut swapped = HashMap::new();
+    swap_inner(root, Priority(level), Priority(level + 1), &mut swapped)
+}
+
+fn swap_inner(root: Node, upper: Priority, lower: Priority, swapped: &mut HashMap<NodeId, Node>) -> Node {
+    let (id, low, high) = match root {
+        Node::Branch(id, low, high) if id <= lower => (id, low, high),
+        _ => return root,
+    };
+
+    if let Some(node) = swapped.get(&NodeId::from(root)) {
+        return *node;
+    }
+
+    let node = if id < upper {
+        let low = swap_inner(low.into(), upper, lower, swapped);
+        let high = swap_inner(high.into(), upper, lower, swapped);
+
+        Node::branch(id, low, high)
+    } else if id == upper {
+        let (low_without, low_with) = split(low.into(), lower);
+        let (high_without, high_with) = split(high.into(), lower);
+
+        Node::branch(upper, Node::branch(lower, low_without, high_without), Node::branch(lower, low_with, high_with))
+    } else {
+        Node::branch(upper, low, high)
+    };
+
+    swapped.insert(NodeId::from(root), node);
+    node
+}
+
+fn split(root: Node, priority: Priority) -> (Node, Node) {
+    match root {
+        Node::Branch(id, low, high) if id == priority => (low.into(), high.into()),
+        _ => (root, Node::Never),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hashbrown::HashMap;
+
+    use super::super::Node;
+    use super::super::Priority;
+
+    /// Each pair {i, i + count} appears together or not at all, with at least one pair in every set
+    fn pairs(count: usize) -> Node {
+        (0..count)
+            .map(|i| Node::branch(Priority(i), Node::Always, Node::branch(Priority(i + count), Node::Never, Node::Always)))
+            .fold(Node::Always, Node::product)
+            .difference(Node::Always)
+    }
+
+    #[test]
+    fn swap_exchanges_adjacent_levels() {
+        // {{0}, {0, 1}, {1, 2}}
+        let root = node! {
+            id: 0,
+            low: node!(id: 1, low: node!(Never), high: node!(id: 2)),
+            high: node!(id: 1, low: node!(Always))
+        };
+        let priorities: HashMap<_, _> = vec![
+            (Priority(0), Priority(1)),
+            (Priority(1), Priority(0)),
+            (Priority(2), Priority(2)),
+        ].into_iter().collect();
+
+        assert_eq!(Node::translate(root, &priorities), Node::swap(root, 0));
+    }
+
+    #[test]
+    fn swap_relabels_levels_without_the_upper_priority() {
+        let root = node!(id: 2, low: node!(id: 3), high: node!(Always));
+
+        assert_eq!(node!(id: 1, low: node!(id: 3), high: node!(Always)), Node::swap(root, 1));
+    }
+
+    #[test]
+    fn swap_twice_is_the_same_diagram() {
+        let root = pairs(3);
+
+        assert_eq!(root, Node::swap(Node::swap(root, 2), 2));
+    }
+
+    #[test]
+    fn sift_shrinks_separated_pairs() {
+        let root = pairs(4);
+
+        let (sifted, priorities) = Node::sift(root, 8);
+
+        assert!(Node::size(sifted) < Node::size(root));
+        assert_eq!(Node::translate(root, &priorities), sifted);
+        assert_eq!(root.count(), sifted.count());
+    }
+
+    #[test]
+    fn sift_of_leaves() {
+        assert_eq!(Node::Always, Node::sift(Node::Always, 0).0);
+        assert_eq!(Node::Never, Node::sift(Node::Never, 2).0);
+    }
+}
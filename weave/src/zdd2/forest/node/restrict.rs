@@ -0,0 +1,171 @@
+use super::cache::memoize;
+use super::cache::Operation;
+use super::difference::difference;
+use super::Node;
+use super::subset::cofactors;
+use super::top;
+use super::union::union;
+
+/// The sets of `root` that are supersets of some set in `other`
+pub fn restrict(root: Node, other: Node) -> Node {
+    if root == other {
+        return root;
+    }
+
+    match (root, other) {
+        (Node::Never, _) | (_, Node::Never) => Node::Never,
+        _ if contains_empty(other) => root,
+        (Node::Always, _) => Node::Never,
+
+        _ => memoize(Operation::Restrict, (root, other), || restrict_inner(root, other)),
+    }
+}
+
+fn restrict_inner(root: Node, other: Node) -> Node {
+    let id = top(root, other);
+
+    let (root_low, root_high) = cofactors(root, id);
+    let (other_low, other_high) = cofactors(other, id);
+
+    let low = restrict(root_low, other_low);
+    let high = restrict(root_high, union(other_low, other_high));
+
+    Node::branch(id, low, high)
+}
+
+/// The sets of `root` that are subsets of some set in `other`
+pub fn permit(root: Node, other: Node) -> Node {
+    if root == other {
+        return root;
+    }
+
+    match (root, other) {
+        (Node::Never, _) | (_, Node::Never) => Node::Never,
+        (Node::Always, _) => Node::Always,
+        (_, Node::Always) => if contains_empty(root) { Node::Always } else { Node::Never },
+
+        _ => memoize(Operation::Permit, (root, other), || permit_inner(root, other)),
+    }
+}
+
+fn permit_inner(root: Node, other: Node) -> Node {
+    let id = top(root, other);
+
+    let (root_low, root_high) = cofactors(root, id);
+    let (other_low, other_high) = cofactors(other, id);
+
+    let low = permit(root_low, union(other_low, other_high));
+    let high = permit(root_high, other_high);
+
+    Node::branch(id, low, high)
+}
+
+/// The sets of `root` that are not supersets of any set in `other`
+pub fn nonsup(root: Node, other: Node) -> Node {
+    difference(root, restrict(root, other))
+}
+
+/// The sets of `root` that are not subsets of any set in `other`
+pub fn nonsub(root: Node, other: Node) -> Node {
+    difference(root, permit(root, other))
+}
+
+/// Whether the family rooted at `root` holds the empty set, found by following low edges down to a leaf
+pub fn contains_empty(root: Node) -> bool {
+    let mut root = root;
+    while let Node::Branch(_, low, _) = root {
+        root = low.into();
+    }
+
+    root == Node::Always
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::Node;
+
+    fn family() -> Node {
+        // {{0, 1}, {1, 2}, {2}}
+        node! {
+            id: 0,
+            low: node! {
+                id: 1,
+                low: node!(id: 2),
+                high: node!(id: 2)
+            },
+            high: node!(id: 1)
+        }
+    }
+
+    #[test]
+    fn restrict_keeps_supersets() {
+        // supersets of {{1}} == {{0, 1}, {1, 2}}
+        let expected = node! {
+            id: 0,
+            low: node!(id: 1, low: node!(Never), high: node!(id: 2)),
+            high: node!(id: 1)
+        };
+
+        assert_eq!(expected, Node::restrict(family(), node!(id: 1)));
+    }
+
+    #[test]
+    fn restrict_by_family_with_empty_set_is_identity() {
+        let other = node!(id: 3, low: node!(Always));
+
+        assert_eq!(family(), Node::restrict(family(), other));
+        assert_eq!(Node::Never, Node::restrict(family(), Node::Never));
+    }
+
+    #[test]
+    fn permit_keeps_subsets() {
+        // subsets of {{1, 2}} == {{1, 2}, {2}}
+        let other = node!(id: 1, low: node!(Never), high: node!(id: 2));
+
+        let expected = node! {
+            id: 1,
+            low: node!(id: 2),
+            high: node!(id: 2)
+        };
+
+        assert_eq!(expected, Node::permit(family(), other));
+    }
+
+    #[test]
+    fn permit_by_always_keeps_only_the_empty_set() {
+        let root = node!(id: 0, low: node!(Always));
+
+        assert_eq!(Node::Always, Node::permit(root, Node::Always));
+        assert_eq!(Node::Never, Node::permit(family(), Node::Always));
+    }
+
+    #[test]
+    fn nonsup_and_restrict_partition_the_family() {
+        let other = node!(id: 1);
+
+        let restricted = Node::restrict(family(), other);
+        let nonsup = Node::nonsup(family(), other);
+
+        assert_eq!(node!(id: 2), nonsup);
+        assert_eq!(family(), Node::union(restricted, nonsup));
+    }
+
+    #[test]
+    fn nonsub_and_permit_partition_the_family() {
+        let other = node!(id: 1, low: node!(Never), high: node!(id: 2));
+
+        let permitted = Node::permit(family(), other);
+        let nonsub = Node::nonsub(family(), other);
+
+        assert_eq!(node!(id: 0, low: node!(Never), high: node!(id: 1)), nonsub);
+        assert_eq!(family(), Node::union(permitted, nonsub));
+    }
+
+    #[test]
+    fn contains_empty_follows_low_edges() {
+        assert!(super::contains_empty(Node::Always));
+        assert!(super::contains_empty(node!(id: 0, low: node!(id: 1, low: node!(Always)))));
+        assert!(!super::contains_empty(family()));
+        assert!(!super::contains_empty(Node::Never));
+    }
+}
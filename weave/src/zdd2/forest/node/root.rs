@@ -0,0 +1,105 @@
+use std::fmt;
+
+use serde::de::{Deserialize, Deserializer};
+use serde::ser::{Serialize, Serializer};
+
+use super::Manager;
+use super::Node;
+use super::NodeId;
+
+/// Root retains a node in its manager's arena, keeping it and everything below it alive across collections
+#[derive(Eq, PartialEq, Hash)]
+pub struct Root {
+    manager: Manager,
+    id: NodeId,
+}
+
+impl Root {
+    /// Roots `node` in the manager of the running operation
+    pub fn new(node: impl Into<NodeId>) -> Self {
+        let id = node.into();
+        let manager = Manager::with_current(Manager::clone);
+        manager.retain(id);
+
+        Root { manager, id }
+    }
+
+    pub fn id(&self) -> NodeId {
+        self.id
+    }
+
+    pub fn manager(&self) -> &Manager {
+        &self.manager
+    }
+
+    /// This root's node, copied into `manager` if it is bound to another one
+    ///
+    /// Must be called while an operation on `manager` is running
+    pub fn node_in(&self, manager: &Manager) -> Node {
+        manager.import(&self.manager, self.id)
+    }
+}
+
+impl Clone for Root {
+    fn clone(&self) -> Self {
+        self.manager.retain(self.id);
+
+        Root { manager: self.manager.clone(), id: self.id }
+    }
+}
+
+impl Drop for Root {
+    fn drop(&mut self) {
+        self.manager.release(self.id);
+    }
+}
+
+impl fmt::Debug for Root {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let _operation = self.manager.operation();
+
+        write!(f, "{:?}", self.id)
+    }
+}
+
+impl Serialize for Root {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let _operation = self.manager.operation();
+
+        self.id.serialize(serializer)
+    }
+}
+
+/// Deserialized roots are bound to the global manager
+impl<'de> Deserialize<'de> for Root {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let _operation = Manager::global().operation();
+
+        NodeId::deserialize(deserializer).map(Root::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Manager;
+    use super::Root;
+
+    #[test]
+    fn root_is_retained_until_every_clone_is_dropped() {
+        let manager = Manager::new();
+
+        let clone = {
+            let _operation = manager.operation();
+
+            let root = Root::new(node!(id: 12_345));
+            root.clone()
+        };
+
+        assert_eq!(0, manager.collect().reclaimed);
+        assert_eq!(&manager, clone.manager());
+
+        drop(clone);
+
+        assert_eq!(1, manager.collect().reclaimed);
+    }
+}
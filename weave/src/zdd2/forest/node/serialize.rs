@@ -1,10 +1,15 @@
 use std::fmt;
 
-use serde::de::{Deserialize, Deserializer, Error, Visitor};
+use serde::de::{Deserialize, Deserializer, Error, MapAccess, Unexpected, Visitor};
+use serde::de::value::MapAccessDeserializer;
 use serde::ser::{Serialize, Serializer};
 
+use table::Entry;
+use table::NodeTable;
+
 use super::Node;
 use super::NodeId;
+use super::Priority;
 use super::parser;
 
 impl Serialize for NodeId {
@@ -16,8 +21,13 @@ impl Serialize for NodeId {
 
 impl Serialize for Node {
     fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
-        let node_str = parser::build_node_string(*self);
-        serializer.serialize_newtype_struct("Node", &node_str)
+        let table = NodeTable::write(NodeId::from(self), |node_id| match Node::from(node_id) {
+            Node::Branch(priority, low, high) => Entry::Branch(priority.0, low, high),
+            Node::Always => Entry::Leaf(true),
+            Node::Never => Entry::Leaf(false),
+        });
+
+        serializer.serialize_newtype_struct("Node", &table)
     }
 }
 
@@ -31,23 +41,46 @@ impl<'de> Visitor<'de> for NodeVisitor {
     }
 
     fn visit_newtype_struct<D: Deserializer<'de>>(self, deserializer: D) -> Result<Node, D::Error> {
-        deserializer.deserialize_str(NodeStringVisitor)
+        if deserializer.is_human_readable() {
+            return deserializer.deserialize_any(NodeTableVisitor);
+        }
+
+        NodeTable::deserialize(deserializer)
+            .and_then(read_table)
     }
 }
 
-struct NodeStringVisitor;
+/// NodeTableVisitor reads a node table, or the nested string that self-describing formats held before node tables
+struct NodeTableVisitor;
 
-impl<'de> Visitor<'de> for NodeStringVisitor {
+impl<'de> Visitor<'de> for NodeTableVisitor {
     type Value = Node;
 
     fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-        formatter.write_str("string, formatted as a Node")
+        formatter.write_str("struct NodeTable or a nested Node string")
     }
 
     fn visit_str<E: Error>(self, node_str: &str) -> Result<Node, E> {
+        if !NodeTable::is_nested(node_str) {
+            return Err(Error::invalid_value(Unexpected::Str(node_str), &self));
+        }
+
         parser::parse_node_string(node_str)
             .map_err(Error::custom)
     }
+
+    fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Node, A::Error> {
+        NodeTable::deserialize(MapAccessDeserializer::new(map))
+            .and_then(read_table)
+    }
+}
+
+fn read_table<E: Error>(table: NodeTable) -> Result<Node, E> {
+    let leaf = |always| if always { Node::Always } else { Node::Never };
+    let branch = |priority, low, high| Node::branch(Priority(priority), low, high);
+
+    table.read(leaf, branch)
+        .map_err(Error::custom)
 }
 
 impl<'de> Deserialize<'de> for NodeId {
@@ -65,25 +98,41 @@ impl<'de> Deserialize<'de> for Node {
 
 #[cfg(test)]
 mod tests {
-    use serde_test::{assert_tokens, Token};
+    use serde_test::{assert_de_tokens, assert_de_tokens_error, assert_tokens, Compact, Configure, Readable, Token};
 
     #[test]
     fn tokenize_leaves() {
         {
             let node = node!(Always);
 
-            assert_tokens(&node, &[
+            assert_tokens(&node.readable(), &[
                 Token::NewtypeStruct { name: "Node" },
-                Token::Str("(A)"),
+                Token::Struct { name: "NodeTable", len: 3 },
+                Token::Str("version"),
+                Token::U64(2),
+                Token::Str("nodes"),
+                Token::Seq { len: Some(0) },
+                Token::SeqEnd,
+                Token::Str("root"),
+                Token::U64(1),
+                Token::StructEnd,
             ]);
         }
 
         {
             let node = node!(Never);
 
-            assert_tokens(&node, &[
+            assert_tokens(&node.readable(), &[
                 Token::NewtypeStruct { name: "Node" },
-                Token::Str("(N)"),
+                Token::Struct { name: "NodeTable", len: 3 },
+                Token::Str("version"),
+                Token::U64(2),
+                Token::Str("nodes"),
+                Token::Seq { len: Some(0) },
+                Token::SeqEnd,
+                Token::Str("root"),
+                Token::U64(0),
+                Token::StructEnd,
             ]);
         }
     }
@@ -92,9 +141,23 @@ mod tests {
     fn tokenize_branch() {
         let node = node!(id: 1);
 
-        assert_tokens(&node, &[
+        assert_tokens(&node.readable(), &[
             Token::NewtypeStruct { name: "Node" },
-            Token::Str("(1 (N) (A))"),
+            Token::Struct { name: "NodeTable", len: 3 },
+            Token::Str("version"),
+            Token::U64(2),
+            Token::Str("nodes"),
+            Token::Seq { len: Some(1) },
+            Token::Tuple { len: 4 },
+            Token::U64(2),
+            Token::U64(1),
+            Token::U64(0),
+            Token::U64(1),
+            Token::TupleEnd,
+            Token::SeqEnd,
+            Token::Str("root"),
+            Token::U64(2),
+            Token::StructEnd,
         ]);
     }
 
@@ -106,9 +169,135 @@ mod tests {
             high: node!(id: 2)
         };
 
-        assert_tokens(&node, &[
+        assert_tokens(&node.readable(), &[
+            Token::NewtypeStruct { name: "Node" },
+            Token::Struct { name: "NodeTable", len: 3 },
+            Token::Str("version"),
+            Token::U64(2),
+            Token::Str("nodes"),
+            Token::Seq { len: Some(2) },
+            Token::Tuple { len: 4 },
+            Token::U64(2),
+            Token::U64(2),
+            Token::U64(0),
+            Token::U64(1),
+            Token::TupleEnd,
+            Token::Tuple { len: 4 },
+            Token::U64(3),
+            Token::U64(1),
+            Token::U64(1),
+            Token::U64(2),
+            Token::TupleEnd,
+            Token::SeqEnd,
+            Token::Str("root"),
+            Token::U64(3),
+            Token::StructEnd,
+        ]);
+    }
+
+    #[test]
+    fn tokenize_shared_branches_once() {
+        let node = node! {
+            id: 0,
+            low: node!(id: 2),
+            high: node!(id: 1, low: node!(Always), high: node!(id: 2))
+        };
+
+        assert_tokens(&node.readable(), &[
+            Token::NewtypeStruct { name: "Node" },
+            Token::Struct { name: "NodeTable", len: 3 },
+            Token::Str("version"),
+            Token::U64(2),
+            Token::Str("nodes"),
+            Token::Seq { len: Some(3) },
+            Token::Tuple { len: 4 },
+            Token::U64(2),
+            Token::U64(2),
+            Token::U64(0),
+            Token::U64(1),
+            Token::TupleEnd,
+            Token::Tuple { len: 4 },
+            Token::U64(3),
+            Token::U64(1),
+            Token::U64(1),
+            Token::U64(2),
+            Token::TupleEnd,
+            Token::Tuple { len: 4 },
+            Token::U64(4),
+            Token::U64(0),
+            Token::U64(2),
+            Token::U64(3),
+            Token::TupleEnd,
+            Token::SeqEnd,
+            Token::Str("root"),
+            Token::U64(4),
+            Token::StructEnd,
+        ]);
+    }
+
+    #[test]
+    fn detokenize_nested_strings() {
+        let node = node! {
+            id: 1,
+            low: node!(Always),
+            high: node!(id: 2)
+        };
+
+        assert_de_tokens(&node.readable(), &[
             Token::NewtypeStruct { name: "Node" },
             Token::Str("(1 (A) (2 (N) (A)))"),
         ]);
     }
+
+    #[test]
+    fn detokenize_tables_in_compact_formats() {
+        let node = node!(id: 1);
+
+        assert_de_tokens(&node.compact(), &[
+            Token::NewtypeStruct { name: "Node" },
+            Token::Struct { name: "NodeTable", len: 3 },
+            Token::Str("version"),
+            Token::U64(2),
+            Token::Str("nodes"),
+            Token::Seq { len: Some(1) },
+            Token::Tuple { len: 4 },
+            Token::U64(2),
+            Token::U64(1),
+            Token::U64(0),
+            Token::U64(1),
+            Token::TupleEnd,
+            Token::SeqEnd,
+            Token::Str("root"),
+            Token::U64(2),
+            Token::StructEnd,
+        ]);
+    }
+
+    #[test]
+    fn detokenize_unknown_version_fails() {
+        assert_de_tokens_error::<Readable<super::Node>>(&[
+            Token::NewtypeStruct { name: "Node" },
+            Token::Struct { name: "NodeTable", len: 3 },
+            Token::Str("version"),
+            Token::U64(3),
+            Token::Str("nodes"),
+            Token::Seq { len: Some(0) },
+            Token::SeqEnd,
+            Token::Str("root"),
+            Token::U64(1),
+            Token::StructEnd,
+        ], "Unsupported node table version: 3");
+    }
+
+    #[test]
+    fn detokenize_strings_other_than_nested_fails() {
+        assert_de_tokens_error::<Compact<super::Node>>(&[
+            Token::NewtypeStruct { name: "Node" },
+            Token::Str("2;1"),
+        ], "invalid type: string \"2;1\", expected struct NodeTable");
+        assert_de_tokens_error::<Readable<super::Node>>(&[
+            Token::NewtypeStruct { name: "Node" },
+            Token::Str("2;1"),
+        ], "invalid value: string \"2;1\", expected struct NodeTable or a nested Node string");
+    }
 }
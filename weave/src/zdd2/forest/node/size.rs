@@ -0,0 +1,123 @@
+use super::cache::memoize;
+use super::cache::Operation;
+use super::Node;
+use super::Priority;
+use super::restrict::contains_empty;
+
+/// The sets of the family rooted at `root` with at least `min` and at most `max` elements
+pub fn with_size(root: Node, min: usize, max: usize) -> Node {
+    match root {
+        _ if min > max => Node::Never,
+        Node::Never => Node::Never,
+        Node::Always if min == 0 => Node::Always,
+        Node::Always => Node::Never,
+        _ if max == 0 => if contains_empty(root) { Node::Always } else { Node::Never },
+
+        _ => memoize(Operation::WithSize(min, max), (root, Node::Never), || with_size_inner(root, min, max)),
+    }
+}
+
+fn with_size_inner(root: Node, min: usize, max: usize) -> Node {
+    match root {
+        Node::Branch(id, low, high) => {
+            let low = with_size(low.into(), min, max);
+            let high = with_size(high.into(), min.saturating_sub(1), max - 1);
+
+            Node::branch(id, low, high)
+        }
+        _ => unreachable!("Only branches are memoized"),
+    }
+}
+
+/// Every set of exactly `k` of the given elements, built bottom up with one node per element and count
+pub fn choose(elements: &[Priority], k: usize) -> Node {
+    let mut elements = elements.to_vec();
+    elements.sort();
+    elements.dedup();
+
+    if k > elements.len() {
+        return Node::Never;
+    }
+
+    // below[r] holds the sets of `r` elements chosen from those below the current one
+    let mut below: Vec<Node> = (0..=k)
+        .map(|r| if r == 0 { Node::Always } else { Node::Never })
+        .collect();
+
+    for element in elements.into_iter().rev() {
+        below = (0..=k)
+            .map(|r| {
+                let high = if r == 0 { Node::Never } else { below[r - 1] };
+
+                Node::branch(element, below[r], high)
+            })
+            .collect();
+    }
+
+    below[k]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::Node;
+    use super::super::Priority;
+
+    #[test]
+    fn with_size_of_leaves() {
+        assert_eq!(Node::Never, Node::Never.with_size(0, 3));
+        assert_eq!(Node::Always, Node::Always.with_size(0, 3));
+        assert_eq!(Node::Never, Node::Always.with_size(1, 3));
+    }
+
+    #[test]
+    fn with_size_keeps_sets_within_bounds() {
+        // {{0}, {0, 1}, {1, 2}, {0, 1, 2}}
+        let root = node! {
+            id: 0,
+            low: node!(id: 1, low: node!(Never), high: node!(id: 2)),
+            high: node!(id: 1, low: node!(Always), high: node!(id: 2, low: node!(Always)))
+        };
+
+        assert_eq!(node!(id: 0), root.with_size(1, 1));
+        assert_eq!(
+            node! {
+                id: 0,
+                low: node!(id: 1, low: node!(Never), high: node!(id: 2)),
+                high: node!(id: 1)
+            },
+            root.with_size(2, 2)
+        );
+        assert_eq!(root, root.with_size(0, 3));
+        assert_eq!(Node::Never, root.with_size(2, 1));
+    }
+
+    #[test]
+    fn with_size_zero_keeps_only_the_empty_set() {
+        let root = node!(id: 0, low: node!(Always));
+
+        assert_eq!(Node::Always, root.with_size(0, 0));
+        assert_eq!(Node::Never, node!(id: 0).with_size(0, 0));
+    }
+
+    #[test]
+    fn choose_builds_every_combination() {
+        // {{0, 1}, {0, 2}, {1, 2}}
+        let expected = node! {
+            id: 0,
+            low: node!(id: 1, low: node!(Never), high: node!(id: 2)),
+            high: node!(id: 1, low: node!(id: 2), high: node!(Always))
+        };
+
+        assert_eq!(expected, Node::choose(&[Priority(2), Priority(0), Priority(1)], 2));
+        assert_eq!(3, Node::choose(&[Priority(0), Priority(1), Priority(2)], 2).count());
+    }
+
+    #[test]
+    fn choose_edge_cases() {
+        let elements = [Priority(0), Priority(1)];
+
+        assert_eq!(Node::Always, Node::choose(&elements, 0));
+        assert_eq!(Node::Never, Node::choose(&elements, 3));
+        assert_eq!(node!(id: 0, low: node!(Never), high: node!(id: 1)), Node::choose(&elements, 2));
+    }
+}
@@ -1,32 +1,32 @@
+use super::cache::memoize;
+use super::change::change;
+use super::cache::Operation;
 use super::Node;
 use super::Priority;
 
-type Matching = (Node, bool);
-
 pub fn subset(root: Node, element: Priority) -> Node {
-    subset_inner(root, element).0
-}
-
-fn subset_inner(root: Node, element: Priority) -> Matching {
     match root {
         Node::Branch(id, _low, high) if element == id => {
             let low = Node::NEVER;
 
-            (Node::branch(id, low, high), true)
+            Node::branch(id, low, high)
         }
-        Node::Branch(id, low, high) => {
-            let (low, keep_low) = reduce_branch(
-                subset_inner(low.into(), element)
-            );
-            let (high, keep_high) = reduce_branch(
-                subset_inner(high.into(), element)
-            );
+        Node::Branch(id, _, _) if id < element => {
+            memoize(Operation::Subset(element), (root, Node::Never), || subset_inner(root, element))
+        }
+        _ => Node::Never,
+    }
+}
 
-            let keep = keep_low || keep_high;
+fn subset_inner(root: Node, element: Priority) -> Node {
+    match root {
+        Node::Branch(id, low, high) => {
+            let low = subset(low.into(), element);
+            let high = subset(high.into(), element);
 
-            (Node::branch(id, low, high), keep)
+            Node::branch(id, low, high)
         }
-        _ => (root, false),
+        _ => unreachable!("Only branches above the element are memoized"),
     }
 }
 
@@ -35,31 +35,45 @@ pub fn subset_not(root: Node, element: Priority) -> Node {
         Node::Branch(id, low, _high) if element == id => {
             Node::from(low)
         }
+        Node::Branch(id, _, _) if id < element => {
+            memoize(Operation::SubsetNot(element), (root, Node::Never), || subset_not_inner(root, element))
+        }
+        _ => root,
+    }
+}
+
+fn subset_not_inner(root: Node, element: Priority) -> Node {
+    match root {
         Node::Branch(id, low, high) => {
             let low = subset_not(low.into(), element);
             let high = subset_not(high.into(), element);
 
             Node::branch(id, low, high)
         }
-        _ => root,
+        _ => unreachable!("Only branches above the element are memoized"),
+    }
+}
+
+/// Splits the family rooted at `root` into the sets without `element` and the sets with it, `element` removed
+pub fn cofactors(root: Node, element: Priority) -> (Node, Node) {
+    match root {
+        Node::Branch(id, low, high) if id == element => (low.into(), high.into()),
+        Node::Branch(id, _, _) if id < element => {
+            let low = subset_not(root, element);
+            let high = change(subset(root, element), element);
+
+            (low, high)
+        }
+        _ => (root, Node::Never),
     }
 }
 
 pub fn subset_all(root: Node, elements: &[Priority]) -> Node {
     elements.iter()
-        .map(|element| subset(root, element.to_owned()))
-        .fold(Node::Always, Node::intersect)
+        .fold(root, |root, element| subset(root, *element))
 }
 
 pub fn subset_none(root: Node, elements: &[Priority]) -> Node {
     elements.iter()
-        .map(|element| subset_not(root, element.to_owned()))
-        .fold(Node::Always, Node::intersect)
-}
-
-fn reduce_branch((root, keep): Matching) -> Matching {
-    match (root, keep) {
-        (_root, false) => (Node::Never, keep),
-        (root, true) => (root, keep)
-    }
+        .fold(root, |root, element| subset_not(root, *element))
 }
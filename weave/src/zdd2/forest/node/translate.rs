@@ -0,0 +1,107 @@
+use hashbrown::HashMap;
+use itertools::Itertools;
+
+use super::Node;
+use super::NodeId;
+use super::Priority;
+
+/// Rebuilds the family rooted at `root` with every priority replaced according to `priorities`
+pub fn translate(root: Node, priorities: &HashMap<Priority, Priority>) -> Node {
+    if priorities.iter().all(|(old, new)| old == new) {
+        return root;
+    }
+
+    let preserves_order = priorities.iter()
+        .sorted()
+        .tuple_windows()
+        .all(|((_, new1), (_, new2))| new1 < new2);
+
+    let mut translated = HashMap::new();
+    translate_inner(root, priorities, preserves_order, &mut translated)
+}
+
+fn translate_inner(root: Node, priorities: &HashMap<Priority, Priority>, preserves_order: bool, translated: &mut HashMap<NodeId, Node>) -> Node {
+    let (id, low, high) = match root {
+        Node::Branch(id, low, high) => (id, low, high),
+        _ => return root,
+    };
+
+    if let Some(node) = translated.get(&NodeId::from(root)) {
+        return *node;
+    }
+
+    let low = translate_inner(low.into(), priorities, preserves_order, translated);
+    let high = translate_inner(high.into(), priorities, preserves_order, translated);
+    let id = *priorities.get(&id)
+        .unwrap_or_else(|| panic!("Expected priority to be translated: {:?}", id));
+
+    let node = if preserves_order {
+        Node::branch(id, low, high)
+    } else {
+        Node::union(low, Node::change(high, id))
+    };
+
+    translated.insert(NodeId::from(root), node);
+    node
+}
+
+#[cfg(test)]
+mod tests {
+    use hashbrown::HashMap;
+
+    use super::super::Node;
+    use super::super::Priority;
+
+    #[test]
+    fn translate_relabels_when_order_is_kept() {
+        let root = node! {
+            id: 0,
+            low: node!(Never),
+            high: node!(id: 1)
+        };
+        let priorities: HashMap<_, _> = vec![
+            (Priority(0), Priority(1)),
+            (Priority(1), Priority(3)),
+        ].into_iter().collect();
+
+        let expected = node! {
+            id: 1,
+            low: node!(Never),
+            high: node!(id: 3)
+        };
+
+        assert_eq!(expected, Node::translate(root, &priorities));
+    }
+
+    #[test]
+    fn translate_reorders_when_order_changes() {
+        // {{0, 2}, {1, 2}}
+        let root = node! {
+            id: 0,
+            low: node! {
+                id: 1,
+                low: node!(Never),
+                high: node!(id: 2)
+            },
+            high: node!(id: 2)
+        };
+        let priorities: HashMap<_, _> = vec![
+            (Priority(0), Priority(1)),
+            (Priority(1), Priority(2)),
+            (Priority(2), Priority(0)),
+        ].into_iter().collect();
+
+        // {{1, 0}, {2, 0}}
+        let expected = node! {
+            id: 0,
+            low: node!(Never),
+            high: node! {
+                id: 1,
+                low: node!(id: 2),
+                high: node!(Always)
+            }
+        };
+
+        assert_eq!(expected, Node::translate(root, &priorities));
+    }
+}
@@ -1,3 +1,5 @@
+use super::cache::memoize;
+use super::cache::Operation;
 use super::Node;
 
 pub fn union(node1: Node, node2: Node) -> Node {
@@ -5,12 +7,18 @@ pub fn union(node1: Node, node2: Node) -> Node {
         return node1;
     }
 
-    let (id, low, high) = match (node1, node2) {
-        (_, Node::Never) => return node1,
-        (Node::Never, _) => return node2,
+    match (node1, node2) {
+        (_, Node::Never) => node1,
+        (Node::Never, _) => node2,
+
+        (Node::Always, Node::Always) => Node::Always,
 
-        (Node::Always, Node::Always) => return Node::Always,
+        _ => memoize(Operation::Union, (node1, node2), || union_inner(node1, node2)),
+    }
+}
 
+fn union_inner(node1: Node, node2: Node) -> Node {
+    let (id, low, high) = match (node1, node2) {
         (Node::Branch(id, low, high), Node::Always) => {
             let low = union(low.into(), node2);
             let high = Node::from(high);
@@ -42,6 +50,8 @@ pub fn union(node1: Node, node2: Node) -> Node {
 
             (id_1, low, high)
         }
+
+        _ => unreachable!("Terminal unions are resolved before memoization"),
     };
 
     Node::branch(id, low, high)
@@ -0,0 +1,85 @@
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+
+    use super::super::Forest;
+    use super::super::Universe;
+
+    /// Each of "a{i}" and "b{i}" appears only alongside the other, for every combination of pairs
+    fn pairs(count: usize) -> Vec<Vec<String>> {
+        (1..(1 << count))
+            .map(|chosen: usize| {
+                (0..count)
+                    .filter(|i| chosen & (1 << i) != 0)
+                    .flat_map(|i| vec![format!("a{}", i), format!("b{}", i)])
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn interleaved(count: usize) -> Vec<String> {
+        (0..count)
+            .flat_map(|i| vec![format!("a{}", i), format!("b{}", i)])
+            .collect()
+    }
+
+    fn sorted(forest: &Forest<String>) -> Vec<Vec<String>> {
+        forest.trees().into_iter().sorted().collect()
+    }
+
+    #[test]
+    fn many_with_order_uses_the_order() {
+        let forest = Forest::many_with_order(&pairs(4), &interleaved(4));
+
+        assert_eq!(interleaved(4), forest.order());
+        assert_eq!(sorted(&Forest::many(&pairs(4))), sorted(&forest));
+        assert!(forest.node_count() < Forest::many(&pairs(4)).node_count());
+    }
+
+    #[test]
+    fn many_with_order_puts_unordered_items_last() {
+        let forest = Forest::many_with_order(&[vec!["1", "2"], vec!["2", "3"]], &["3"]);
+
+        assert_eq!(vec!["3", "2", "1"], forest.order());
+    }
+
+    #[test]
+    fn many_with_universe_sorts_by_the_comparator() {
+        let universe = Universe::builder()
+            .compare_by(|item1: &String, item2: &String| item1[1..].cmp(&item2[1..]).then(item1.cmp(item2)));
+
+        let forest = Forest::many_with_universe(&pairs(3), &universe);
+
+        assert_eq!(interleaved(3), forest.order());
+    }
+
+    #[test]
+    fn order_survives_combining_with_a_default_forest() {
+        let ordered = Forest::many_with_order(&pairs(3), &interleaved(3));
+        let other = Forest::many(&[vec![String::from("c"), String::from("a0")]]);
+
+        let union = ordered.clone().union(other.clone());
+        assert_eq!([interleaved(3), vec![String::from("c")]].concat(), union.order());
+
+        let union = other.union(ordered);
+        assert_eq!([interleaved(3), vec![String::from("c")]].concat(), union.order());
+    }
+
+    #[test]
+    fn order_survives_operations_that_drop_items() {
+        let ordered = Forest::many_with_order(&pairs(3), &interleaved(3));
+
+        let subset = ordered.subset_not(String::from("a1"));
+
+        assert_eq!(vec!["a0", "b0", "a2", "b2"], subset.order());
+    }
+
+    #[test]
+    fn left_order_wins_when_both_forests_have_one() {
+        let forest1 = Forest::many_with_order(&[vec!["1", "2", "3"]], &["3", "2", "1"]);
+        let forest2 = Forest::many_with_order(&[vec!["1", "2"]], &["1", "2"]);
+
+        assert_eq!(vec!["3", "2", "1"], forest1.clone().union(forest2.clone()).order());
+        assert_eq!(vec!["1", "2", "3"], forest2.union(forest1).order());
+    }
+}
@@ -184,4 +184,18 @@ mod tests {
             Forest::product(forest1, forest2)
         );
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn product_with_itself_adds_pairwise_unions() {
+        let forest = Forest::unique(&["1", "2"]);
+
+        assert_eq!(
+            Forest::many(&[
+                vec!["1"],
+                vec!["1", "2"],
+                vec!["2"],
+            ]),
+            Forest::product(forest.clone(), forest)
+        );
+    }
+}
@@ -0,0 +1,134 @@
+use std::hash::Hash;
+
+use itertools::Itertools;
+
+use super::Forest;
+use super::Node;
+use super::Priority;
+use super::sample;
+
+/// Walks the path of `set`, counting the sets that come before it whenever it skips a high edge
+pub fn rank<T>(forest: &Forest<T>, set: &[T]) -> Option<u128>
+    where T: Hash + Eq + Clone + Ord + Sync + Send {
+    let priorities: Option<Vec<Priority>> = set.iter()
+        .map(|item| forest.universe.get_priority(item))
+        .collect();
+    let mut priorities = priorities?.into_iter().sorted().dedup().peekable();
+
+    let _operation = forest.manager().operation();
+
+    let counts = Node::from(forest.root.id()).counts();
+
+    let mut rank = 0;
+    let mut id = forest.root.id();
+    while let Node::Branch(priority, low, high) = Node::from(id) {
+        id = match priorities.peek() {
+            Some(next) if *next < priority => return None,
+            Some(next) if *next == priority => {
+                priorities.next();
+                high
+            }
+            _ => {
+                rank += counts[&high];
+                low
+            }
+        };
+    }
+
+    if Node::from(id) == Node::Always && priorities.peek().is_none() {
+        Some(rank)
+    } else {
+        None
+    }
+}
+
+pub fn unrank<T>(forest: &Forest<T>, index: u128) -> Option<Vec<T>>
+    where T: Hash + Eq + Clone + Ord + Sync + Send {
+    let _operation = forest.manager().operation();
+
+    let counts = Node::from(forest.root.id()).counts();
+    if index >= counts[&forest.root.id()] {
+        return None;
+    }
+
+    Some(sample::pick(forest, &counts, index))
+}
+
+#[cfg(test)]
+mod rank_tests {
+    use super::super::fixtures::product_of_groups;
+    use super::super::Forest;
+
+    #[test]
+    fn rank_is_the_position_in_trees() {
+        let forest = Forest::many(&[
+            vec!["1", "2"],
+            vec!["1", "3"],
+            vec!["2", "3"],
+            vec!["4"],
+        ]);
+
+        for (index, tree) in forest.trees().iter().enumerate() {
+            assert_eq!(Some(index as u128), forest.rank(tree));
+        }
+    }
+
+    #[test]
+    fn unrank_returns_the_set_at_each_position() {
+        let forest = Forest::many(&[
+            vec!["1", "2"],
+            vec!["1", "3"],
+            vec!["2", "3"],
+            vec!["4"],
+        ]);
+
+        let unranked: Vec<_> = (0..4)
+            .map(|index| forest.unrank(index).unwrap())
+            .collect();
+
+        assert_eq!(forest.trees(), unranked);
+        assert_eq!(None, forest.unrank(4));
+    }
+
+    #[test]
+    fn rank_of_non_members_is_none() {
+        let forest = Forest::many(&[
+            vec!["1", "2"],
+            vec!["1", "3"],
+            vec!["2", "3"],
+            vec!["4"],
+        ]);
+
+        assert_eq!(None, forest.rank(&["1"]));
+        assert_eq!(None, forest.rank(&["1", "2", "3"]));
+        assert_eq!(None, forest.rank(&["1", "5"]));
+        assert_eq!(None, forest.rank(&[]));
+        assert_eq!(None, Forest::<&str>::empty().rank(&[]));
+    }
+
+    #[test]
+    fn rank_ignores_item_order_and_repeats() {
+        let forest = Forest::many(&[
+            vec!["1", "2"],
+            vec!["1", "3"],
+            vec!["2", "3"],
+            vec!["4"],
+        ]);
+
+        assert_eq!(forest.rank(&["2", "3"]), forest.rank(&["3", "2", "3"]));
+    }
+
+    #[test]
+    fn rank_and_unrank_round_trip_in_huge_forests() {
+        let forest = product_of_groups(40, 3);
+        let count = forest.count();
+
+        for index in &[0, 1, count / 3, count / 2 + 7, count - 1] {
+            let set = forest.unrank(*index).unwrap();
+
+            assert_eq!(41, set.len());
+            assert_eq!(Some(*index), forest.rank(&set));
+        }
+        assert_eq!(None, forest.unrank(count));
+    }
+}
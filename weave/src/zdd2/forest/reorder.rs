@@ -0,0 +1,70 @@
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+
+    use super::super::Forest;
+    use super::super::Manager;
+
+    /// Items "a{i}" and "b{i}" always appear together, which the default order keeps far apart
+    fn pairs_in(count: usize, manager: &Manager) -> Forest<String> {
+        (0..count)
+            .map(|i| Forest::unit_in(&[format!("a{}", i), format!("b{}", i)], manager))
+            .fold(Forest::empty_in(manager), |forest, pair| {
+                let with_pair = forest.clone().product(pair.clone());
+
+                forest.union(pair).union(with_pair)
+            })
+    }
+
+    fn sorted(forest: &Forest<String>) -> Vec<Vec<String>> {
+        forest.trees().into_iter().sorted().collect()
+    }
+
+    #[test]
+    fn sift_keeps_the_family_and_shrinks_the_diagram() {
+        let forest = pairs_in(5, Manager::global());
+
+        let sifted = forest.clone().sift();
+
+        assert_eq!(sorted(&forest), sorted(&sifted));
+        assert_eq!(forest, sifted);
+        assert!(sifted.node_count() < forest.node_count());
+    }
+
+    #[test]
+    fn sifted_order_survives_unary_operations() {
+        let forest = pairs_in(5, Manager::global());
+        let sifted = forest.clone().sift();
+        let order = sifted.order();
+
+        let without_pair: Vec<_> = order.iter()
+            .filter(|item| *item != "a0" && *item != "b0")
+            .cloned()
+            .collect();
+
+        assert_ne!(forest.order(), order);
+        assert_eq!(order, sifted.clone().subset(String::from("a0")).order());
+        assert_eq!(without_pair, sifted.clone().subset_not(String::from("a0")).order());
+        assert_eq!(order, sifted.clone().minimal().order());
+        assert_eq!(order, sifted.maximal().order());
+    }
+
+    #[test]
+    fn sift_of_empty_forest() {
+        let forest: Forest<String> = Forest::empty();
+
+        assert!(forest.sift().is_empty());
+    }
+
+    #[test]
+    fn operations_sift_automatically_past_the_threshold() {
+        let manual = pairs_in(6, &Manager::new());
+
+        let manager = Manager::new();
+        manager.auto_reorder(Some(1));
+        let automatic = pairs_in(6, &manager);
+
+        assert_eq!(sorted(&manual), sorted(&automatic));
+        assert!(automatic.node_count() < manual.node_count());
+    }
+}
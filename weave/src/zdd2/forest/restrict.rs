@@ -0,0 +1,142 @@
+#[cfg(test)]
+mod restrict_tests {
+    use super::super::Forest;
+
+    #[test]
+    fn restrict_keeps_sets_including_a_bundle() {
+        let forest = Forest::many(&[
+            vec!["a", "b", "c"],
+            vec!["a", "d"],
+            vec!["b", "c"],
+            vec!["c"],
+        ]);
+
+        let bundles = Forest::many(&[
+            vec!["b", "c"],
+            vec!["d"],
+        ]);
+
+        assert_eq!(
+            Forest::many(&[
+                vec!["a", "b", "c"],
+                vec!["a", "d"],
+                vec!["b", "c"],
+            ]),
+            forest.restrict(bundles)
+        );
+    }
+
+    #[test]
+    fn restrict_by_unknown_items_is_empty() {
+        let forest = Forest::many(&[
+            vec!["a", "b", "c"],
+            vec!["a", "d"],
+            vec!["b", "c"],
+            vec!["c"],
+        ]);
+
+        assert_eq!(
+            Forest::empty(),
+            forest.restrict(Forest::unit(&["e"]))
+        );
+    }
+
+    #[test]
+    fn nonsup_keeps_sets_including_no_bundle() {
+        let forest = Forest::many(&[
+            vec!["a", "b", "c"],
+            vec!["a", "d"],
+            vec!["b", "c"],
+            vec!["c"],
+        ]);
+
+        let bundles = Forest::many(&[
+            vec!["b", "c"],
+            vec!["d"],
+        ]);
+
+        assert_eq!(
+            Forest::unit(&["c"]),
+            forest.nonsup(bundles)
+        );
+    }
+
+    #[test]
+    fn nonsup_by_empty_is_identity() {
+        let forest = Forest::many(&[
+            vec!["a", "b", "c"],
+            vec!["a", "d"],
+            vec!["b", "c"],
+            vec!["c"],
+        ]);
+
+        assert_eq!(
+            forest,
+            forest.clone().nonsup(Forest::empty())
+        );
+    }
+}
+
+#[cfg(test)]
+mod permit_tests {
+    use super::super::Forest;
+
+    #[test]
+    fn permit_keeps_sets_within_a_kit() {
+        let forest = Forest::many(&[
+            vec!["a", "b", "c"],
+            vec!["a", "d"],
+            vec!["b", "c"],
+            vec!["c"],
+        ]);
+
+        let kits = Forest::many(&[
+            vec!["b", "c", "d"],
+            vec!["a", "d", "e"],
+        ]);
+
+        assert_eq!(
+            Forest::many(&[
+                vec!["a", "d"],
+                vec!["b", "c"],
+                vec!["c"],
+            ]),
+            forest.permit(kits)
+        );
+    }
+
+    #[test]
+    fn nonsub_keeps_sets_within_no_kit() {
+        let forest = Forest::many(&[
+            vec!["a", "b", "c"],
+            vec!["a", "d"],
+            vec!["b", "c"],
+            vec!["c"],
+        ]);
+
+        let kits = Forest::many(&[
+            vec!["b", "c", "d"],
+            vec!["a", "d", "e"],
+        ]);
+
+        assert_eq!(
+            Forest::unit(&["a", "b", "c"]),
+            forest.nonsub(kits)
+        );
+    }
+
+    #[test]
+    fn permit_by_itself_is_identity() {
+        let forest = Forest::many(&[
+            vec!["a", "b", "c"],
+            vec!["a", "d"],
+            vec!["b", "c"],
+            vec!["c"],
+        ]);
+
+        assert_eq!(
+            forest,
+            forest.clone().permit(forest.clone())
+        );
+    }
+}
@@ -0,0 +1,168 @@
+use std::collections::BTreeSet;
+use std::hash::Hash;
+
+use hashbrown::HashMap;
+use rand::Rng;
+
+use super::Forest;
+use super::Node;
+use super::NodeId;
+
+pub fn sample<T, R>(forest: &Forest<T>, rng: &mut R) -> Option<Vec<T>>
+    where T: Hash + Eq + Clone + Ord + Sync + Send, R: Rng + ?Sized {
+    let _operation = forest.manager().operation();
+
+    let counts = Node::from(forest.root.id()).counts();
+    let count = counts[&forest.root.id()];
+    if count == 0 {
+        return None;
+    }
+
+    let index = rng.gen_range(0, count);
+    Some(pick(forest, &counts, index))
+}
+
+/// Floyd's algorithm draws `n` distinct indices without materializing the ones that are skipped
+pub fn sample_n<T, R>(forest: &Forest<T>, rng: &mut R, n: usize) -> Vec<Vec<T>>
+    where T: Hash + Eq + Clone + Ord + Sync + Send, R: Rng + ?Sized {
+    let _operation = forest.manager().operation();
+
+    let counts = Node::from(forest.root.id()).counts();
+    let count = counts[&forest.root.id()];
+    let n = count.min(n as u128);
+
+    let mut indices = BTreeSet::new();
+    for upper in (count - n)..count {
+        let index = rng.gen_range(0, upper + 1);
+        if !indices.insert(index) {
+            indices.insert(upper);
+        }
+    }
+
+    indices.into_iter()
+        .map(|index| pick(forest, &counts, index))
+        .collect()
+}
+
+/// Walks down to the set at `index`, in the same order as `Forest::trees`
+pub fn pick<T>(forest: &Forest<T>, counts: &HashMap<NodeId, u128>, mut index: u128) -> Vec<T>
+    where T: Hash + Eq + Clone + Ord + Sync + Send {
+    let mut path = vec![];
+    let mut id = forest.root.id();
+
+    while let Node::Branch(priority, low, high) = Node::from(id) {
+        let high_count = counts[&high];
+        if index < high_count {
+            path.push(priority);
+            id = high;
+        } else {
+            index -= high_count;
+            id = low;
+        }
+    }
+
+    forest.universe.get_items(&path)
+}
+
+#[cfg(test)]
+mod sample_tests {
+    use hashbrown::HashMap;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use super::super::fixtures::product_of_groups;
+    use super::super::Forest;
+
+    #[test]
+    fn sample_of_empty_returns_none() {
+        let forest: Forest<&str> = Forest::empty();
+        let mut rng = StdRng::seed_from_u64(7);
+
+        assert_eq!(None, forest.sample(&mut rng));
+    }
+
+    #[test]
+    fn sample_returns_members() {
+        let forest = Forest::many(&[
+            vec!["1", "2"],
+            vec!["1", "3"],
+            vec!["2", "3"],
+            vec!["4"],
+        ]);
+        let trees = forest.trees();
+        let mut rng = StdRng::seed_from_u64(7);
+
+        for _ in 0..100 {
+            let tree = forest.sample(&mut rng).unwrap();
+
+            assert!(trees.contains(&tree));
+        }
+    }
+
+    #[test]
+    fn sample_is_uniform() {
+        let forest = Forest::many(&[
+            vec!["1", "2"],
+            vec!["1", "3"],
+            vec!["2", "3"],
+            vec!["4"],
+        ]);
+        let mut rng = StdRng::seed_from_u64(7);
+
+        let mut frequencies = HashMap::new();
+        for _ in 0..4_000 {
+            *frequencies.entry(forest.sample(&mut rng).unwrap()).or_insert(0) += 1;
+        }
+
+        assert_eq!(4, frequencies.len());
+        for frequency in frequencies.values() {
+            assert!(*frequency > 850 && *frequency < 1_150, "frequency {} is not near 1000", frequency);
+        }
+    }
+
+    #[test]
+    fn sample_n_returns_distinct_members_in_tree_order() {
+        let forest = Forest::many(&[
+            vec!["1", "2"],
+            vec!["1", "3"],
+            vec!["2", "3"],
+            vec!["4"],
+        ]);
+        let trees = forest.trees();
+        let mut rng = StdRng::seed_from_u64(7);
+
+        let sample = forest.sample_n(&mut rng, 3);
+
+        assert_eq!(3, sample.len());
+        assert!(sample.iter().all(|tree| trees.contains(tree)));
+
+        let positions: Vec<_> = sample.iter()
+            .map(|tree| trees.iter().position(|t| t == tree).unwrap())
+            .collect();
+        assert!(positions.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn sample_n_larger_than_forest_returns_every_member() {
+        let forest = Forest::many(&[
+            vec!["1", "2"],
+            vec!["1", "3"],
+            vec!["2", "3"],
+            vec!["4"],
+        ]);
+        let mut rng = StdRng::seed_from_u64(7);
+
+        assert_eq!(forest.trees(), forest.sample_n(&mut rng, 10));
+    }
+
+    #[test]
+    fn sample_of_huge_forest_does_not_enumerate() {
+        let forest = product_of_groups(40, 4);
+        let mut rng = StdRng::seed_from_u64(7);
+
+        let sample = forest.sample_n(&mut rng, 5);
+
+        assert_eq!(5, sample.len());
+        assert!(sample.iter().all(|tree| tree.len() == 41));
+    }
+}
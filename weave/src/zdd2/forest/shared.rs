@@ -0,0 +1,108 @@
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::super::Forest;
+    use super::super::Universe;
+
+    fn catalog() -> Arc<Universe<&'static str>> {
+        Universe::from_matrix(&[
+            vec!["jeans", "shirt", "boots"],
+            vec!["shorts", "shirt", "sandals"],
+            vec!["jeans", "sweater", "boots"],
+        ]).share()
+    }
+
+    #[test]
+    fn forests_from_one_catalog_share_its_universe() {
+        let catalog = catalog();
+
+        let forest1 = Forest::many_shared(&[vec!["jeans", "shirt"]], &catalog);
+        let forest2 = Forest::many_shared(&[vec!["shorts", "sandals"], vec!["jeans"]], &catalog);
+
+        assert!(Arc::ptr_eq(&catalog, forest1.universe()));
+        assert!(Arc::ptr_eq(&catalog, forest2.universe()));
+
+        let union = forest1.union(forest2);
+
+        assert!(Arc::ptr_eq(&catalog, union.universe()));
+        assert_eq!(
+            Forest::many(&[
+                vec!["jeans", "shirt"],
+                vec!["shorts", "sandals"],
+                vec!["jeans"],
+            ]),
+            union
+        );
+    }
+
+    #[test]
+    fn operations_over_a_shared_universe_keep_it() {
+        let catalog = catalog();
+        let forest = Forest::many_shared(&[vec!["jeans", "shirt"], vec!["shorts", "boots"]], &catalog);
+
+        let product = forest.clone().product(Forest::many_shared(&[vec!["sweater"]], &catalog));
+        let subset = forest.subset("jeans");
+
+        assert!(Arc::ptr_eq(&catalog, product.universe()));
+        assert!(Arc::ptr_eq(&catalog, subset.universe()));
+        assert_eq!(Forest::many(&[vec!["jeans", "shirt"]]), subset);
+        assert_eq!(
+            Forest::many(&[
+                vec!["jeans", "shirt", "sweater"],
+                vec!["shorts", "boots", "sweater"],
+            ]),
+            product
+        );
+    }
+
+    #[test]
+    fn new_items_extend_a_copy_of_the_universe() {
+        let catalog = catalog();
+        let forest1 = Forest::many_shared(&[vec!["jeans", "shirt"]], &catalog);
+
+        let forest2 = Forest::many_shared(&[vec!["jeans", "hat"]], &catalog);
+
+        assert!(!Arc::ptr_eq(&catalog, forest2.universe()));
+        assert!(forest2.universe().extends(&catalog));
+        assert_eq!(Some(&"hat"), forest2.order().last());
+
+        let union = forest1.union(forest2.clone());
+
+        assert!(Arc::ptr_eq(forest2.universe(), union.universe()));
+        assert_eq!(
+            Forest::many(&[
+                vec!["jeans", "shirt"],
+                vec!["jeans", "hat"],
+            ]),
+            union
+        );
+    }
+
+    #[test]
+    fn occurrences_count_only_the_forests_own_sets() {
+        let forest = Forest::many_shared(&[vec!["jeans", "shirt"], vec!["jeans"]], &catalog());
+
+        assert_eq!(vec![("jeans", 2), ("shirt", 1)], forest.occurrences());
+    }
+
+    #[test]
+    fn shared_forests_combine_with_unshared_ones() {
+        let forest = Forest::many_shared(&[vec!["jeans", "shirt"]], &catalog());
+
+        let union = forest.union(Forest::many(&[vec!["hat"]]));
+
+        assert_eq!(Forest::many(&[vec!["jeans", "shirt"], vec!["hat"]]), union);
+    }
+
+    #[test]
+    fn shared_forests_with_different_sets_are_not_equal() {
+        let catalog = catalog();
+
+        assert_ne!(
+            Forest::many_shared(&[vec!["jeans", "shirt"]], &catalog),
+            Forest::many_shared(&[vec!["jeans"]], &catalog)
+        );
+        assert_ne!(Forest::many(&[vec!["jeans"]]), Forest::many_shared(&[vec!["jeans", "shirt"]], &catalog));
+    }
+}
@@ -0,0 +1,105 @@
+#[cfg(test)]
+mod tests {
+    use super::super::Forest;
+
+    #[test]
+    fn with_size_exactly() {
+        let forest = Forest::many(&[
+            vec!["a"],
+            vec!["a", "b"],
+            vec!["b", "c"],
+            vec!["a", "b", "c"],
+            vec!["a", "b", "c", "d"],
+        ]);
+
+        assert_eq!(
+            Forest::many(&[
+                vec!["a", "b"],
+                vec!["b", "c"],
+            ]),
+            forest.with_size(2..=2)
+        );
+    }
+
+    #[test]
+    fn with_size_at_most() {
+        let forest = Forest::many(&[
+            vec!["a"],
+            vec!["a", "b"],
+            vec!["b", "c"],
+            vec!["a", "b", "c"],
+            vec!["a", "b", "c", "d"],
+        ]);
+
+        assert_eq!(
+            Forest::many(&[
+                vec!["a"],
+                vec!["a", "b"],
+                vec!["b", "c"],
+            ]),
+            forest.with_size(..3)
+        );
+    }
+
+    #[test]
+    fn with_size_at_least() {
+        let forest = Forest::many(&[
+            vec!["a"],
+            vec!["a", "b"],
+            vec!["b", "c"],
+            vec!["a", "b", "c"],
+            vec!["a", "b", "c", "d"],
+        ]);
+
+        assert_eq!(
+            Forest::many(&[
+                vec!["a", "b", "c"],
+                vec!["a", "b", "c", "d"],
+            ]),
+            forest.with_size(3..)
+        );
+    }
+
+    #[test]
+    fn with_size_outside_the_forest_is_empty() {
+        let forest = Forest::many(&[
+            vec!["a"],
+            vec!["a", "b"],
+            vec!["b", "c"],
+            vec!["a", "b", "c"],
+            vec!["a", "b", "c", "d"],
+        ]);
+
+        assert_eq!(Forest::empty(), forest.clone().with_size(5..));
+        assert_eq!(Forest::empty(), forest.clone().with_size(..0));
+        assert_eq!(forest, forest.clone().with_size(..));
+    }
+
+    #[test]
+    fn choose_matches_listing_every_combination() {
+        assert_eq!(
+            Forest::many(&[
+                vec!["a", "b"],
+                vec!["a", "c"],
+                vec!["b", "c"],
+            ]),
+            Forest::choose(&["a", "b", "c"], 2)
+        );
+    }
+
+    #[test]
+    fn choose_more_than_there_are_items_is_empty() {
+        assert_eq!(Forest::empty(), Forest::choose(&["a", "b"], 3));
+    }
+
+    #[test]
+    fn choose_from_many_items_is_compact() {
+        let items: Vec<_> = (0..60).collect();
+
+        let forest = Forest::choose(&items, 30);
+
+        assert_eq!(118_264_581_564_861_424, forest.count());
+        assert_eq!(forest.count(), forest.clone().with_size(30..=30).count());
+        assert!(forest.with_size(..30).is_empty());
+    }
+}
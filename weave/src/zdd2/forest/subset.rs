@@ -6,11 +6,13 @@ use super::Priority;
 
 pub fn subset<T: Hash + Eq + Clone + Ord + Sync + Send>(forest: Forest<T>, element: T) -> Forest<T> {
     let element = match forest.universe.get_priority(&element) {
-        None => return Forest::empty(),
+        None => return Forest::empty_in(forest.manager()),
         Some(element) => element,
     };
 
-    let root = Node::subset(forest.root.into(), element);
+    let manager = forest.manager().clone();
+    let _operation = manager.operation();
+    let root = Node::subset(forest.root.id().into(), element);
 
     Forest::canonical(root, forest.universe)
 }
@@ -21,7 +23,9 @@ pub fn subset_not<T: Hash + Eq + Clone + Ord + Sync + Send>(forest: Forest<T>, e
         Some(element) => element,
     };
 
-    let root = Node::subset_not(forest.root.into(), element);
+    let manager = forest.manager().clone();
+    let _operation = manager.operation();
+    let root = Node::subset_not(forest.root.id().into(), element);
 
     Forest::canonical(root, forest.universe)
 }
@@ -41,7 +45,9 @@ pub fn subset_many<T: Hash + Eq + Clone + Ord + Sync + Send>(
     match (elements.len(), known_elements.len()) {
         (l1, l2) if l1 != l2 => default(forest),
         _ => {
-            let root = func(forest.root.into(), &known_elements);
+            let manager = forest.manager().clone();
+            let _operation = manager.operation();
+            let root = func(forest.root.id().into(), &known_elements);
 
             Forest::canonical(root, forest.universe)
         }
@@ -1,31 +1,171 @@
+use std::hash::Hash;
+
+use hashbrown::HashMap;
+
 use super::node::Node;
 use super::node::NodeId;
+use super::node::Root;
 use super::Priority;
+use super::universe::Universe;
+
+/// Trees lazily walks the sets of a forest, one root-to-leaf path at a time
+///
+/// Skipping ahead with `nth` (and so `skip`) jumps over whole sub-diagrams using their set counts
+pub struct Trees<'a, T: 'a + Hash + Eq + Clone + Ord> {
+    root: &'a Root,
+    universe: &'a Universe<T>,
+    counts: HashMap<NodeId, u128>,
+    remaining: u128,
+    stack: Vec<(NodeId, usize)>,
+    path: Vec<Priority>,
+}
 
-pub fn trees(root: NodeId) -> Vec<Vec<Priority>> {
-    let trees: Vec<Vec<Priority>> = {
-        let mut trees = vec![];
+impl<'a, T: Hash + Eq + Clone + Ord> Trees<'a, T> {
+    pub fn new(root: &'a Root, universe: &'a Universe<T>) -> Self {
+        let _operation = root.manager().operation();
+
+        let counts = Node::from(root.id()).counts();
+        let remaining = counts[&root.id()];
+
+        Trees { root, universe, counts, remaining, stack: vec![(root.id(), 0)], path: vec![] }
+    }
+}
 
-        let mut queue: Vec<(Node, Vec<Priority>)> = vec![(Node::from(root), vec![])];
-        while let Some((node, path)) = queue.pop() {
-            match node {
-                Node::Branch(id, low, high) => {
-                    let low = Node::from(low);
-                    let high = Node::from(high);
+impl<'a, T: Hash + Eq + Clone + Ord> Iterator for Trees<'a, T> {
+    type Item = Vec<T>;
 
-                    queue.push((low, path.clone()));
+    fn next(&mut self) -> Option<Vec<T>> {
+        self.nth(0)
+    }
 
-                    let mut path = path.clone();
-                    path.push(id);
-                    queue.push((high, path));
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.remaining > usize::MAX as u128 {
+            (usize::MAX, None)
+        } else {
+            (self.remaining as usize, Some(self.remaining as usize))
+        }
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Vec<T>> {
+        let _operation = self.root.manager().operation();
+        let mut skip = n as u128;
+
+        while let Some((id, depth)) = self.stack.pop() {
+            let count = self.counts[&id];
+            if skip >= count {
+                skip -= count;
+                self.remaining -= count;
+                continue;
+            }
+
+            self.path.truncate(depth);
+            match Node::from(id) {
+                Node::Branch(priority, low, high) => {
+                    self.stack.push((low, depth));
+                    self.stack.push((high, depth + 1));
+                    self.path.push(priority);
+                }
+                Node::Always => {
+                    self.remaining -= 1;
+                    return Some(self.universe.get_items(&self.path));
                 }
-                Node::Always => trees.push(path),
                 Node::Never => {}
-            };
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::Forest;
+
+    #[test]
+    fn iter_of_empty_is_empty() {
+        let forest = Forest::<&str>::empty();
+
+        assert_eq!(None, forest.iter().next());
+        assert_eq!((0, Some(0)), forest.iter().size_hint());
+    }
+
+    #[test]
+    fn iter_yields_every_tree_once() {
+        let forest = Forest::many(&[
+            vec!["1", "2"],
+            vec!["1", "3"],
+            vec!["2", "3"],
+            vec!["2", "4"],
+            vec!["3"],
+        ]);
+
+        let mut trees: Vec<_> = forest.iter().collect();
+        trees.sort();
+
+        assert_eq!(
+            vec![
+                vec!["1", "2"],
+                vec!["1", "3"],
+                vec!["2", "3"],
+                vec!["2", "4"],
+                vec!["3"],
+            ],
+            trees
+        );
+    }
+
+    #[test]
+    fn iter_counts_down_remaining_trees() {
+        let forest = Forest::many(&[
+            vec!["1", "2"],
+            vec!["1", "3"],
+            vec!["2", "3"],
+            vec!["2", "4"],
+            vec!["3"],
+        ]);
+        let mut iter = forest.iter();
+
+        assert_eq!((5, Some(5)), iter.size_hint());
+        iter.next();
+        assert_eq!((4, Some(4)), iter.size_hint());
+        iter.nth(2);
+        assert_eq!((1, Some(1)), iter.size_hint());
+    }
+
+    #[test]
+    fn nth_matches_stepping_one_at_a_time() {
+        let forest = Forest::many(&[
+            vec!["1", "2"],
+            vec!["1", "3"],
+            vec!["2", "3"],
+            vec!["2", "4"],
+            vec!["3"],
+        ]);
+        let trees: Vec<_> = forest.iter().collect();
+
+        for (n, tree) in trees.iter().enumerate() {
+            assert_eq!(Some(tree.clone()), forest.iter().nth(n));
         }
+        assert_eq!(None, forest.iter().nth(trees.len()));
+    }
+
+    #[test]
+    fn skip_and_take_page_through_trees() {
+        let forest = Forest::many(&[
+            vec!["1", "2"],
+            vec!["1", "3"],
+            vec!["2", "3"],
+            vec!["2", "4"],
+            vec!["3"],
+        ]);
+        let trees: Vec<_> = forest.iter().collect();
 
-        trees
-    };
+        let pages: Vec<Vec<_>> = (0..3)
+            .map(|page| forest.iter().skip(page * 2).take(2).collect())
+            .collect();
 
-    trees
+        assert_eq!(trees[0..2].to_vec(), pages[0]);
+        assert_eq!(trees[2..4].to_vec(), pages[1]);
+        assert_eq!(trees[4..].to_vec(), pages[2]);
+    }
 }
@@ -195,4 +195,30 @@ mod tests {
             Forest::union(tree1, tree2)
         );
     }
+
+    #[test]
+    fn union_returns_many_when_merged_universe_reorders_items() {
+        let tree1 = Forest::many(&[
+            vec!["1", "3"],
+            vec!["2", "3"]
+        ]);
+        let tree2 = Forest::many(&[
+            vec!["1"],
+            vec!["1", "4"],
+            vec!["1", "5"],
+            vec!["1", "6"],
+        ]);
+
+        assert_eq!(
+            Forest::many(&[
+                vec!["1"],
+                vec!["1", "3"],
+                vec!["1", "4"],
+                vec!["1", "5"],
+                vec!["1", "6"],
+                vec!["2", "3"],
+            ]),
+            Forest::union(tree1, tree2)
+        );
+    }
 }
@@ -0,0 +1,98 @@
+use std::cmp::Ordering;
+use std::hash::Hash;
+
+use itertools::Itertools;
+
+use super::Order;
+use super::Universe;
+
+type Comparator<T> = Box<dyn Fn(&T, &T) -> Ordering>;
+
+/// UniverseBuilder chooses the item order of a universe instead of leaving it to occurrence counts
+///
+/// Items named by `order` come first, in that order. The rest follow sorted by the comparator, if there is one,
+/// with ties, and everything when there is none, falling back on the default order.
+pub struct UniverseBuilder<T: Hash + Eq + Clone + Ord> {
+    order: Vec<T>,
+    compare: Option<Comparator<T>>,
+}
+
+impl<T: Hash + Eq + Clone + Ord> Default for UniverseBuilder<T> {
+    fn default() -> Self {
+        UniverseBuilder { order: Vec::new(), compare: None }
+    }
+}
+
+impl<T: Hash + Eq + Clone + Ord> UniverseBuilder<T> {
+    pub fn order(mut self, order: &[T]) -> Self {
+        self.order = order.iter().unique().cloned().collect();
+        self
+    }
+
+    pub fn compare_by<F>(mut self, compare: F) -> Self where F: Fn(&T, &T) -> Ordering + 'static {
+        self.compare = Some(Box::new(compare));
+        self
+    }
+
+    /// A universe over the items of `matrix`, counting their occurrences the same way `Universe::from_matrix` does
+    pub fn build(&self, matrix: &[Vec<T>]) -> Universe<T> {
+        let universe = Universe::from_matrix(matrix);
+
+        let mut rest: Vec<T> = universe.index.iter()
+            .filter(|item| !self.order.contains(item))
+            .cloned()
+            .collect();
+        if let Some(compare) = &self.compare {
+            rest.sort_by(|item1, item2| compare(item1, item2));
+        }
+
+        let index = self.order.iter()
+            .filter(|item| universe.occurrences.contains_key(*item))
+            .cloned()
+            .chain(rest)
+            .collect();
+
+        Universe::from_order(index, universe.occurrences, Order::Chosen)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Universe;
+
+    fn matrix() -> Vec<Vec<&'static str>> {
+        vec![
+            vec!["a1", "b1"],
+            vec!["a1", "b2"],
+            vec!["a2", "b1"],
+        ]
+    }
+
+    #[test]
+    fn build_without_an_order_is_the_default_order() {
+        let universe = Universe::builder().build(&matrix());
+
+        assert_eq!(Universe::from_matrix(&matrix()).index, universe.index);
+        assert_eq!(Universe::from_matrix(&matrix()).occurrences, universe.occurrences);
+        assert!(universe.is_ordered());
+    }
+
+    #[test]
+    fn build_puts_ordered_items_first() {
+        let universe = Universe::builder()
+            .order(&["b2", "missing", "a2", "b2"])
+            .build(&matrix());
+
+        assert_eq!(vec!["b2", "a2", "a1", "b1"], universe.index);
+    }
+
+    #[test]
+    fn build_sorts_the_rest_by_the_comparator() {
+        let universe = Universe::builder()
+            .order(&["b1"])
+            .compare_by(|item1: &&str, item2: &&str| item2.cmp(item1))
+            .build(&matrix());
+
+        assert_eq!(vec!["b1", "b2", "a2", "a1"], universe.index);
+    }
+}
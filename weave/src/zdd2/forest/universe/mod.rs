@@ -1,19 +1,35 @@
 use std::cmp::Ordering;
 use std::hash::Hash;
 use std::iter::FromIterator;
+use std::sync::Arc;
 
 use hashbrown::HashMap;
 use itertools::Itertools;
 
 use super::Priority;
 
+pub use self::builder::UniverseBuilder;
+
+mod builder;
 mod serialize;
 
+/// How a universe's items came to be in their order
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Order {
+    /// Sorted by occurrences, and sorted again whenever they change
+    Occurrences,
+    /// Chosen explicitly, and kept in the same relative order as items come and go
+    Chosen,
+    /// Fixed once shared, so every item keeps its priority and new items only ever go after the rest
+    Fixed,
+}
+
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct Universe<T: Hash + Eq + Clone + Ord> {
     occurrences: HashMap<T, usize>,
     priority: HashMap<T, Priority>,
     index: Vec<T>,
+    order: Order,
 }
 
 impl<T: Hash + Eq + Clone + Ord> Default for Universe<T> {
@@ -22,11 +38,17 @@ impl<T: Hash + Eq + Clone + Ord> Default for Universe<T> {
             occurrences: HashMap::new(),
             priority: HashMap::new(),
             index: Vec::new(),
+            order: Order::Occurrences,
         }
     }
 }
 
 impl<T: Hash + Eq + Clone + Ord> Universe<T> {
+    /// Starts a universe whose item order is chosen rather than taken from occurrence counts
+    pub fn builder() -> UniverseBuilder<T> {
+        UniverseBuilder::default()
+    }
+
     pub fn from_items(items: &[T]) -> Self {
         let occurrences: HashMap<T, usize> = items.iter()
             .fold(HashMap::new(), |mut occurrences, item| {
@@ -54,7 +76,34 @@ impl<T: Hash + Eq + Clone + Ord> Universe<T> {
         set.iter().cloned().sorted().unique().collect::<B>()
     }
 
-    pub fn merge(&self, other: &Self) -> Self {
+    /// Fixes this universe's order, so that forests built over it can skip translating each other's items
+    pub fn share(self) -> Arc<Self> {
+        Arc::new(Universe { order: Order::Fixed, ..self })
+    }
+
+    /// Places `items` that are missing from this universe after the rest, leaving every priority as it was
+    pub fn extend(&self, items: &[T]) -> Self {
+        let mut universe = self.clone();
+        for item in items {
+            if !universe.priority.contains_key(item) {
+                universe.priority.insert(item.clone(), Priority(universe.index.len()));
+                universe.occurrences.insert(item.clone(), 0);
+                universe.index.push(item.clone());
+            }
+        }
+
+        universe
+    }
+
+    /// Whether every item of `other` has the same priority in this universe
+    pub fn extends(&self, other: &Self) -> bool {
+        self.index.starts_with(&other.index)
+    }
+
+    /// Combines the items of both universes, keeping an explicit order if either has one, this one's first
+    ///
+    /// The result is fixed if either universe is
+    pub(crate) fn merge(&self, other: &Self) -> Self {
         if self == other {
             return self.clone();
         }
@@ -65,20 +114,77 @@ impl<T: Hash + Eq + Clone + Ord> Universe<T> {
                 occurrences
             });
 
-        Universe::from_occurrences(occurrences)
+        if self.order == Order::Occurrences && other.order == Order::Occurrences {
+            return Universe::from_occurrences(occurrences);
+        }
+
+        let (first, second) = if self.order != Order::Occurrences { (self, other) } else { (other, self) };
+        let index = first.index.iter()
+            .chain(second.index.iter().filter(|item| !first.priority.contains_key(item)))
+            .cloned()
+            .collect();
+        let order = if self.order == Order::Fixed || other.order == Order::Fixed { Order::Fixed } else { Order::Chosen };
+
+        Universe::from_order(index, occurrences, order)
+    }
+
+    /// Keeps only the items with `occurrences`, which become their counts
+    ///
+    /// A fixed universe is returned as it is, since its priorities must not change
+    pub(crate) fn reindex(&self, occurrences: &HashMap<Priority, usize>) -> Self {
+        match self.order {
+            Order::Fixed => self.clone(),
+            Order::Chosen => {
+                let occurrences = self.item_occurrences(occurrences);
+                let index = self.index.iter()
+                    .filter(|item| occurrences.contains_key(item))
+                    .cloned()
+                    .collect();
+
+                Universe::from_order(index, occurrences, Order::Chosen)
+            }
+            Order::Occurrences => self.reindex_by_occurrences(occurrences),
+        }
+    }
+
+    /// Keeps only the items with `occurrences`, sorted in the default order whatever this universe's order is
+    pub(crate) fn reindex_by_occurrences(&self, occurrences: &HashMap<Priority, usize>) -> Self {
+        Universe::from_occurrences(self.item_occurrences(occurrences))
     }
 
-    pub fn get_priority(&self, item: &T) -> Option<Priority> {
+    pub(crate) fn item_occurrences(&self, occurrences: &HashMap<Priority, usize>) -> HashMap<T, usize> {
+        occurrences.iter()
+            .filter_map(|(id, count)| self.get_item(*id).map(|item| (item.clone(), *count)))
+            .collect()
+    }
+
+    /// Moves the item at each old priority to its new one, and keeps that relative order from then on
+    pub(crate) fn permute(&self, priorities: &HashMap<Priority, Priority>) -> Self {
+        let mut index = self.index.clone();
+        for (old, new) in priorities {
+            index[new.0] = self.index[old.0].clone();
+        }
+
+        Universe::from_order(index, self.occurrences.clone(), Order::Chosen)
+    }
+
+    pub(crate) fn translation(&self, new_universe: &Self) -> HashMap<Priority, Priority> {
+        self.priority.iter()
+            .filter_map(|(item, old)| new_universe.get_priority(item).map(|new| (*old, new)))
+            .collect()
+    }
+
+    pub(crate) fn get_priority(&self, item: &T) -> Option<Priority> {
         self.priority.get(item).cloned()
     }
 
-    pub fn get_priorities<B: FromIterator<Priority>>(&self, items: &[T]) -> B {
+    pub(crate) fn get_priorities<B: FromIterator<Priority>>(&self, items: &[T]) -> B {
         items.iter()
             .filter_map(|item| self.get_priority(item))
             .collect::<B>()
     }
 
-    pub fn get_items<B: FromIterator<T>>(&self, ids: &[Priority]) -> B {
+    pub(crate) fn get_items<B: FromIterator<T>>(&self, ids: &[Priority]) -> B {
         ids.iter()
             .filter_map(|id| self.get_item(*id))
             .cloned()
@@ -86,17 +192,39 @@ impl<T: Hash + Eq + Clone + Ord> Universe<T> {
             .collect::<B>()
     }
 
-    pub fn get_item(&self, id: Priority) -> Option<&T> {
+    pub(crate) fn get_item(&self, id: Priority) -> Option<&T> {
         self.index.get(id.0)
     }
 
-    pub fn occurrences(&self) -> &HashMap<T, usize> {
+    pub(crate) fn occurrences(&self) -> &HashMap<T, usize> {
         &self.occurrences
     }
+
+    /// Every item, from the top of a diagram to the bottom
+    pub fn items(&self) -> &[T] {
+        &self.index
+    }
+
+    pub fn len(&self) -> usize {
+        self.index.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.index.is_empty()
+    }
+
+    /// Whether the order was chosen, or fixed, rather than taken from occurrences
+    pub(crate) fn is_ordered(&self) -> bool {
+        self.order != Order::Occurrences
+    }
+
+    pub(crate) fn is_fixed(&self) -> bool {
+        self.order == Order::Fixed
+    }
 }
 
 impl<T: Hash + Eq + Clone + Ord> Universe<T> {
-    fn from_occurrences(occurrences: HashMap<T, usize>) -> Self {
+    pub(crate) fn from_occurrences(occurrences: HashMap<T, usize>) -> Self {
         let ordering: fn(&(&T, &usize), &(&T, &usize)) -> Ordering = |(name1, count1), (name2, count2)| Ord::cmp(count2, count1).then(Ord::cmp(name1, name2));
 
         let priority: HashMap<T, Priority> = occurrences.iter()
@@ -115,6 +243,21 @@ impl<T: Hash + Eq + Clone + Ord> Universe<T> {
             occurrences,
             priority,
             index,
+            order: Order::Occurrences,
+        }
+    }
+
+    fn from_order(index: Vec<T>, occurrences: HashMap<T, usize>, order: Order) -> Self {
+        let priority: HashMap<T, Priority> = index.iter()
+            .enumerate()
+            .map(|(index, item)| (item.clone(), Priority(index)))
+            .collect();
+
+        Universe {
+            occurrences,
+            priority,
+            index,
+            order,
         }
     }
 }
@@ -307,4 +450,121 @@ mod tests {
 
         assert_eq!(expected_universe, Universe::merge(&universe1, &universe2));
     }
+
+    #[test]
+    fn reindex() {
+        let universe = Universe::from_matrix(&[
+            vec!["1", "4", "7"],
+            vec!["1", "4", "8"],
+            vec!["1", "5", "8"],
+        ]);
+        let occurrences: HashMap<Priority, usize> = hashmap! {
+                universe.get_priority(&"1").unwrap() => 1,
+                universe.get_priority(&"8").unwrap() => 2,
+            }.into_iter().collect();
+
+        let expected_universe = Universe::from_matrix(&[
+            vec!["1", "8"],
+            vec!["8"],
+        ]);
+
+        assert_eq!(expected_universe, universe.reindex(&occurrences));
+    }
+
+    #[test]
+    fn translation() {
+        let universe1 = Universe::from_matrix(&[
+            vec!["1", "4"],
+            vec!["1"],
+        ]);
+        let universe2 = Universe::from_matrix(&[
+            vec!["4", "5"],
+            vec!["4"],
+            vec!["1"],
+        ]);
+
+        let expected: HashMap<Priority, Priority> = hashmap! {
+                Priority(0) => Priority(1),
+                Priority(1) => Priority(0),
+            }.into_iter().collect();
+
+        assert_eq!(expected, universe1.translation(&universe2));
+    }
+
+    #[test]
+    fn permute() {
+        let universe = Universe::from_items(&["1", "2", "3"]);
+        let priorities: HashMap<Priority, Priority> = hashmap! {
+                Priority(0) => Priority(2),
+                Priority(1) => Priority(0),
+                Priority(2) => Priority(1),
+            }.into_iter().collect();
+
+        let permuted = universe.permute(&priorities);
+
+        assert_eq!(vec!["2", "3", "1"], permuted.index);
+        assert_eq!(Some(Priority(2)), permuted.get_priority(&"1"));
+        assert_eq!(universe.occurrences, permuted.occurrences);
+    }
+
+    #[test]
+    fn merge_keeps_a_permuted_order() {
+        let universe1 = Universe::from_items(&["1", "2", "3"]);
+        let priorities: HashMap<Priority, Priority> = hashmap! {
+                Priority(0) => Priority(2),
+                Priority(1) => Priority(0),
+                Priority(2) => Priority(1),
+            }.into_iter().collect();
+        let permuted = universe1.permute(&priorities);
+        let universe2 = Universe::from_matrix(&[
+            vec!["0", "1"],
+            vec!["0"],
+        ]);
+
+        assert_eq!(vec!["2", "3", "1", "0"], permuted.merge(&universe2).index);
+        assert_eq!(vec!["2", "3", "1", "0"], universe2.merge(&permuted).index);
+        assert_eq!(Some(&2), universe2.merge(&permuted).occurrences.get("1"));
+    }
+
+    #[test]
+    fn extend_keeps_every_priority() {
+        let universe = Universe::from_items(&["1", "2"]).share();
+
+        let extended = universe.extend(&["0", "2", "3"]);
+
+        assert_eq!(vec!["1", "2", "0", "3"], extended.index);
+        assert!(extended.is_fixed());
+        assert!(extended.extends(&universe));
+        assert!(!universe.extends(&extended));
+    }
+
+    #[test]
+    fn reindex_keeps_a_fixed_universe() {
+        let universe = Universe::from_items(&["1", "2", "3"]).share();
+        let occurrences: HashMap<Priority, usize> = hashmap! {
+                Priority(1) => 4,
+            }.into_iter().collect();
+
+        assert_eq!(*universe, universe.reindex(&occurrences));
+    }
+
+    #[test]
+    fn reindex_keeps_a_permuted_order() {
+        let universe = Universe::from_items(&["1", "2", "3"]);
+        let priorities: HashMap<Priority, Priority> = hashmap! {
+                Priority(0) => Priority(2),
+                Priority(1) => Priority(0),
+                Priority(2) => Priority(1),
+            }.into_iter().collect();
+        let permuted = universe.permute(&priorities);
+
+        let occurrences: HashMap<Priority, usize> = hashmap! {
+                permuted.get_priority(&"1").unwrap() => 5,
+                permuted.get_priority(&"2").unwrap() => 1,
+            }.into_iter().collect();
+        let reindexed = permuted.reindex(&occurrences);
+
+        assert_eq!(vec!["2", "1"], reindexed.index);
+        assert_eq!(Some(&5), reindexed.occurrences.get("1"));
+    }
 }
@@ -0,0 +1,293 @@
+use std::cmp::Ordering;
+use std::hash::Hash;
+use std::ops::Add;
+
+use hashbrown::HashMap;
+
+use super::Forest;
+use super::Node;
+use super::NodeId;
+use super::Priority;
+use super::universe::Universe;
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum Objective {
+    Minimize,
+    Maximize,
+}
+
+impl Objective {
+    /// Whether `weight1` is strictly better than `weight2`; incomparable weights are never better
+    fn prefers<W: PartialOrd>(self, weight1: &W, weight2: &W) -> bool {
+        matches!(
+            (self, weight1.partial_cmp(weight2)),
+            (Objective::Minimize, Some(Ordering::Less)) | (Objective::Maximize, Some(Ordering::Greater))
+        )
+    }
+}
+
+/// Weights looks up, and remembers, the weight of each item in a universe
+struct Weights<'a, T: 'a + Hash + Eq + Clone + Ord, W, F> {
+    universe: &'a Universe<T>,
+    weight: F,
+    weights: HashMap<Priority, W>,
+}
+
+impl<'a, T, W, F> Weights<'a, T, W, F>
+    where T: Hash + Eq + Clone + Ord, W: Copy, F: Fn(&T) -> W {
+    fn new(universe: &'a Universe<T>, weight: F) -> Self {
+        Weights { universe, weight, weights: HashMap::new() }
+    }
+
+    fn get(&mut self, priority: Priority) -> W {
+        let universe = self.universe;
+        let weight = &self.weight;
+
+        *self.weights.entry(priority)
+            .or_insert_with(|| weight(universe.get_item(priority).unwrap()))
+    }
+}
+
+/// Finds the best set with one pass over the node DAG, then walks back down the winning branches
+pub fn best<T, W, F>(forest: &Forest<T>, weight: F, objective: Objective) -> Option<(Vec<T>, W)>
+    where T: Hash + Eq + Clone + Ord + Sync + Send, W: Copy + PartialOrd + Add<Output=W> + Default, F: Fn(&T) -> W {
+    let _operation = forest.manager().operation();
+
+    let mut weights = Weights::new(&forest.universe, weight);
+    let mut best = HashMap::new();
+    let total = best_inner(forest.root.id(), &mut weights, objective, &mut best)?;
+
+    let mut path = vec![];
+    let mut id = forest.root.id();
+    while let Node::Branch(priority, low, high) = Node::from(id) {
+        if best[&id].1 {
+            path.push(priority);
+            id = high;
+        } else {
+            id = low;
+        }
+    }
+
+    Some((forest.universe.get_items(&path), total))
+}
+
+/// Remembers, for each branch, the best weight below it and whether that weight takes the high edge
+fn best_inner<T, W, F>(
+    id: NodeId,
+    weights: &mut Weights<T, W, F>,
+    objective: Objective,
+    best: &mut HashMap<NodeId, (W, bool)>,
+) -> Option<W>
+    where T: Hash + Eq + Clone + Ord, W: Copy + PartialOrd + Add<Output=W> + Default, F: Fn(&T) -> W {
+    let (priority, low, high) = match Node::from(id) {
+        Node::Branch(priority, low, high) => (priority, low, high),
+        Node::Always => return Some(W::default()),
+        Node::Never => return None,
+    };
+
+    if let Some((weight, _)) = best.get(&id) {
+        return Some(*weight);
+    }
+
+    let low = best_inner(low, weights, objective, best);
+    let high = best_inner(high, weights, objective, best)
+        .map(|weight| weights.get(priority) + weight);
+
+    let result = match (low, high) {
+        (Some(low), Some(high)) if objective.prefers(&low, &high) => (low, false),
+        (_, Some(high)) => (high, true),
+        (Some(low), None) => (low, false),
+        (None, None) => return None,
+    };
+
+    best.insert(id, result);
+    Some(result.0)
+}
+
+/// Keeps the `n` best partial sets below each node, merging the lists of both children
+pub fn best_n<T, W, F>(forest: &Forest<T>, n: usize, weight: F, objective: Objective) -> Vec<(Vec<T>, W)>
+    where T: Hash + Eq + Clone + Ord + Sync + Send, W: Copy + PartialOrd + Add<Output=W> + Default, F: Fn(&T) -> W {
+    if n == 0 {
+        return vec![];
+    }
+
+    let _operation = forest.manager().operation();
+
+    let mut weights = Weights::new(&forest.universe, weight);
+    let mut best = HashMap::new();
+    best_n_inner(forest.root.id(), n, &mut weights, objective, &mut best);
+
+    best.remove(&forest.root.id())
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(weight, path)| (forest.universe.get_items(&path), weight))
+        .collect()
+}
+
+fn best_n_inner<T, W, F>(
+    id: NodeId,
+    n: usize,
+    weights: &mut Weights<T, W, F>,
+    objective: Objective,
+    best: &mut HashMap<NodeId, Vec<(W, Vec<Priority>)>>,
+)
+    where T: Hash + Eq + Clone + Ord, W: Copy + PartialOrd + Add<Output=W> + Default, F: Fn(&T) -> W {
+    if best.contains_key(&id) {
+        return;
+    }
+
+    let (priority, low, high) = match Node::from(id) {
+        Node::Branch(priority, low, high) => (priority, low, high),
+        Node::Always => {
+            best.insert(id, vec![(W::default(), vec![])]);
+            return;
+        }
+        Node::Never => {
+            best.insert(id, vec![]);
+            return;
+        }
+    };
+
+    best_n_inner(low, n, weights, objective, best);
+    best_n_inner(high, n, weights, objective, best);
+
+    let weight = weights.get(priority);
+    let mut highs = best[&high].iter()
+        .map(|(total, path)| {
+            let mut path = path.clone();
+            path.push(priority);
+            (weight + *total, path)
+        })
+        .peekable();
+    let mut lows = best[&low].iter().cloned().peekable();
+
+    let mut merged = Vec::with_capacity(n);
+    while merged.len() < n {
+        let take_low = match (lows.peek(), highs.peek()) {
+            (Some((low, _)), Some((high, _))) => objective.prefers(low, high),
+            (Some(_), None) => true,
+            (None, Some(_)) => false,
+            (None, None) => break,
+        };
+
+        let next = if take_low { lows.next() } else { highs.next() };
+        merged.extend(next);
+    }
+
+    best.insert(id, merged);
+}
+
+#[cfg(test)]
+mod weight_tests {
+    use super::super::Forest;
+
+    fn cost(item: &&str) -> i32 {
+        match *item {
+            "a" => 5,
+            "b" => 2,
+            "c" => 1,
+            "d" => 5,
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn best_of_empty_returns_none() {
+        let forest: Forest<&str> = Forest::empty();
+
+        assert_eq!(None, forest.min_weight(cost));
+        assert_eq!(None, forest.max_weight(cost));
+        assert!(forest.min_weight_n(3, cost).is_empty());
+    }
+
+    #[test]
+    fn min_weight_returns_cheapest_set() {
+        let forest = Forest::many(&[
+            vec!["a", "b"],
+            vec!["a", "c"],
+            vec!["b", "c", "d"],
+            vec!["d"],
+        ]);
+
+        assert_eq!(Some((vec!["d"], 5)), forest.min_weight(cost));
+    }
+
+    #[test]
+    fn max_weight_returns_most_expensive_set() {
+        let forest = Forest::many(&[
+            vec!["a", "b"],
+            vec!["a", "c"],
+            vec!["b", "c", "d"],
+            vec!["d"],
+        ]);
+
+        assert_eq!(Some((vec!["b", "c", "d"], 8)), forest.max_weight(cost));
+    }
+
+    #[test]
+    fn weights_may_be_floats() {
+        let forest = Forest::many(&[
+            vec!["a", "b"],
+            vec!["a", "c"],
+            vec!["b", "c", "d"],
+            vec!["d"],
+        ]);
+
+        assert_eq!(
+            Some((vec!["a", "b"], 1.5)),
+            forest.max_weight(|item| if *item == "a" || *item == "b" { 0.75 } else { 0.25 })
+        );
+    }
+
+    #[test]
+    fn min_weight_n_returns_sets_in_order() {
+        let forest = Forest::many(&[
+            vec!["a", "b"],
+            vec!["a", "c"],
+            vec!["b", "c", "d"],
+            vec!["d"],
+        ]);
+
+        assert_eq!(
+            vec![
+                (vec!["d"], 5),
+                (vec!["a", "c"], 6),
+                (vec!["a", "b"], 7),
+            ],
+            forest.min_weight_n(3, cost)
+        );
+    }
+
+    #[test]
+    fn max_weight_n_larger_than_forest_returns_every_set() {
+        let forest = Forest::many(&[
+            vec!["a", "b"],
+            vec!["a", "c"],
+            vec!["b", "c", "d"],
+            vec!["d"],
+        ]);
+
+        let best = forest.max_weight_n(10, cost);
+
+        assert_eq!(4, best.len());
+        assert_eq!(
+            vec![8, 7, 6, 5],
+            best.iter().map(|(_, weight)| *weight).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn min_weight_n_agrees_with_min_weight() {
+        let forest = Forest::many(&[
+            vec!["a", "b"],
+            vec!["a", "c"],
+            vec!["b", "c", "d"],
+            vec!["d"],
+        ]);
+
+        assert_eq!(
+            forest.min_weight(cost),
+            forest.min_weight_n(1, cost).into_iter().next()
+        );
+    }
+}
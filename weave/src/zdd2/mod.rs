@@ -2,7 +2,16 @@ use std::hash::Hash;
 
 use types;
 
+pub use self::forest::CacheConfig;
+pub use self::forest::collect;
+pub use self::forest::Collection;
+pub use self::forest::configure_cache;
+pub use self::forest::Eviction;
 pub use self::forest::Forest;
+pub use self::forest::Manager;
+pub use self::forest::Trees;
+pub use self::forest::Universe;
+pub use self::forest::UniverseBuilder;
 pub use self::tree::Tree;
 
 mod forest;
@@ -53,6 +62,14 @@ impl<T: Hash + Eq + Clone + Ord + Sync + Send> types::Forest<T> for Forest<T> {
         Forest::product(self, other)
     }
 
+    fn difference(self, other: Self) -> Self {
+        Forest::difference(self, other)
+    }
+
+    fn symmetric_difference(self, other: Self) -> Self {
+        Forest::symmetric_difference(self, other)
+    }
+
     fn subset(self, element: T) -> Self {
         Forest::subset(self, element)
     }
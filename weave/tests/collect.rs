@@ -0,0 +1,81 @@
+extern crate weave;
+
+#[cfg(test)]
+mod zdd2_collect_tests {
+    use weave::zdd2::collect;
+    use weave::zdd2::Forest;
+
+    #[test]
+    fn collect_reclaims_dropped_forests_and_keeps_live_ones() {
+        let live = Forest::many(&[
+            vec!["1", "2"],
+            vec!["2", "3"],
+        ]);
+
+        {
+            let garbage = Forest::unique(&["4", "5", "6"])
+                .product(Forest::unique(&["7", "8", "9"]));
+
+            assert_eq!(9, garbage.len());
+        }
+
+        let collection = collect();
+
+        assert!(collection.reclaimed > 0);
+        assert!(collection.live > 0);
+        assert_eq!(0, collect().reclaimed);
+
+        assert_eq!(
+            vec![
+                vec!["1", "2"],
+                vec!["2", "3"],
+            ],
+            live.trees()
+        );
+        assert_eq!(
+            Forest::many(&[
+                vec!["1", "2"],
+                vec!["2", "3"],
+                vec!["3", "4"],
+            ]),
+            live.union(Forest::unit(&["3", "4"]))
+        );
+    }
+}
+
+#[cfg(test)]
+mod zdd_collect_tests {
+    use weave::zdd::collect;
+    use weave::zdd::Universe;
+
+    #[test]
+    fn collect_reclaims_dropped_trees_and_keeps_live_ones() {
+        let universe = Universe::from(vec!["1", "2", "3", "4", "5"]);
+        let live = universe.hyper_tree(&[
+            vec!["1", "2"],
+            vec!["2", "3"],
+        ]);
+
+        {
+            let garbage = universe.unique_tree(&["1", "2"])
+                .product(&universe.unique_tree(&["3", "4", "5"]));
+
+            assert_eq!(6, garbage.combinations().len());
+        }
+
+        let collection = collect();
+
+        assert!(collection.reclaimed > 0);
+        assert!(collection.live > 0);
+        assert_eq!(0, collect().reclaimed);
+
+        assert_eq!(
+            universe.hyper_tree(&[
+                vec!["1", "2"],
+                vec!["2", "3"],
+                vec!["3", "4"],
+            ]),
+            live.union(&universe.tree(&["3", "4"]))
+        );
+    }
+}
@@ -138,4 +138,18 @@ pub fn forests_are_have_multiple_commonality<'a, F: Forest<&'a str> + Debug + Eq
     ]);
 
     (forest1, forest2, expected)
-}
\ No newline at end of file
+}
+pub fn forests_have_nested_sets<'a, F: Forest<&'a str> + Debug + Eq + Clone>() -> (F, F, F) {
+    let forest1 = F::many(&[
+        vec!["1"],
+        vec!["1", "2"],
+    ]);
+    let forest2 = F::many(&[
+        vec!["1", "2", "3"],
+        vec!["1", "2"],
+    ]);
+
+    let expected = F::unit(&["1", "2"]);
+
+    (forest1, forest2, expected)
+}
@@ -1,3 +1,4 @@
+pub mod difference;
 pub mod intersect;
 pub mod product;
 pub mod occurrences;
@@ -5,6 +6,7 @@ pub mod subset;
 pub mod subset_not;
 pub mod subset_all;
 pub mod subset_none;
+pub mod symmetric_difference;
 pub mod union;
 
 macro_rules! intersect {
@@ -39,6 +41,8 @@ macro_rules! intersect_tests {
             intersect!($forest, forests_are_have_single_commonality);
 
             intersect!($forest, forests_are_have_multiple_commonality);
+
+            intersect!($forest, forests_have_nested_sets);
         }
     };
 }
@@ -113,6 +117,70 @@ macro_rules! product_tests {
     };
 }
 
+macro_rules! difference {
+    ($forest:ty, $test_case:ident) => {
+
+        #[test]
+        fn $test_case() {
+            let (forest1, forest2, expected) = $crate::forest::difference::$test_case::<$forest>();
+
+            assert_eq!(
+                expected,
+                <$forest>::difference(forest1, forest2)
+            );
+        }
+    };
+}
+
+macro_rules! symmetric_difference {
+    ($forest:ty, $test_case:ident) => {
+        spec!($forest, $test_case, symmetric_difference);
+    };
+}
+
+macro_rules! difference_tests {
+    ($forest:ty) => {
+
+        #[cfg(test)]
+        mod difference_tests {
+            difference!($forest, both_forests_are_empty);
+
+            difference!($forest, left_is_empty_right_is_many);
+
+            difference!($forest, left_is_many_right_is_empty);
+
+            difference!($forest, forests_are_equal_unit);
+
+            difference!($forest, forests_are_disjoint_units);
+
+            difference!($forest, left_is_unit_right_is_many);
+
+            difference!($forest, left_is_many_right_is_unit);
+
+            difference!($forest, forests_have_commonality);
+
+            difference!($forest, forests_have_nested_sets);
+        }
+
+        #[cfg(test)]
+        mod symmetric_difference_tests {
+            symmetric_difference!($forest, both_forests_are_empty);
+
+            symmetric_difference!($forest, left_is_empty_right_is_many);
+
+            symmetric_difference!($forest, forests_are_equal_many);
+
+            symmetric_difference!($forest, forests_are_disjoint_units);
+
+            symmetric_difference!($forest, left_is_unit_right_is_many);
+
+            symmetric_difference!($forest, forests_have_commonality);
+
+            symmetric_difference!($forest, forests_have_nested_sets);
+        }
+    };
+}
+
 macro_rules! subset {
     ($forest:ty, $test_case:ident) => {
 
@@ -11,6 +11,8 @@ mod matrix_tests {
 
     product_tests!(weave::matrix::Forest<&str>);
 
+    difference_tests!(weave::matrix::Forest<&str>);
+
     subset_tests!(weave::matrix::Forest<&str>);
 
     occurrences_tests!(weave::matrix::Forest<&str>);
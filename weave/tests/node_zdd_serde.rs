@@ -3,7 +3,7 @@ extern crate weave;
 
 #[cfg(test)]
 mod tests {
-    use serde_test::{assert_tokens, Token};
+    use serde_test::{assert_de_tokens, assert_tokens, Configure, Token};
 
     use weave::Universe;
 
@@ -16,7 +16,67 @@ mod tests {
 
         let tree = universe.hyper_tree(&[vec![item1.clone(), item2.clone()]]);
 
-        assert_tokens(&tree, &[
+        assert_tokens(&tree.readable(), &[
+            Token::Struct { name: "Tree", len: 2 },
+            Token::Str("root"),
+
+            Token::NewtypeStruct { name: "Node" },
+            Token::Struct { name: "NodeTable", len: 3 },
+            Token::Str("version"),
+            Token::U64(2),
+            Token::Str("nodes"),
+            Token::Seq { len: Some(2) },
+            Token::Tuple { len: 4 },
+            Token::U64(2),
+            Token::U64(1),
+            Token::U64(0),
+            Token::U64(1),
+            Token::TupleEnd,
+            Token::Tuple { len: 4 },
+            Token::U64(3),
+            Token::U64(0),
+            Token::U64(0),
+            Token::U64(2),
+            Token::TupleEnd,
+            Token::SeqEnd,
+            Token::Str("root"),
+            Token::U64(3),
+            Token::StructEnd,
+
+            Token::Str("universe"),
+            Token::Struct { name: "Universe", len: 2 },
+            Token::Str("items"),
+
+            Token::Seq { len: Some(2) },
+            Token::BorrowedStr("1"),
+            Token::BorrowedStr("2"),
+            Token::SeqEnd,
+
+            Token::Str("item_index"),
+
+            Token::Map { len: Some(2) },
+            Token::BorrowedStr("1"),
+            Token::U64(0),
+            Token::BorrowedStr("2"),
+            Token::U64(1),
+            Token::MapEnd,
+
+            Token::StructEnd,
+
+            Token::StructEnd,
+        ]);
+    }
+
+    #[test]
+    fn detokenize_nested_node() {
+        let item1 = "1";
+        let item2 = "2";
+
+        let universe = Universe::from(vec![item1, item2]);
+
+        let tree = universe.hyper_tree(&[vec![item1, item2]]);
+
+        assert_de_tokens(&tree.readable(), &[
             Token::Struct { name: "Tree", len: 2 },
             Token::Str("root"),
 
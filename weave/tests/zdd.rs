@@ -1,3 +1,5 @@
+extern crate bincode;
+extern crate serde_json;
 extern crate serde_test;
 extern crate weave;
 
@@ -12,6 +14,8 @@ mod zdd_tests {
 
     product_tests!(weave::zdd2::Forest<&str>);
 
+    difference_tests!(weave::zdd2::Forest<&str>);
+
     subset_tests!(weave::zdd2::Forest<&str>);
 
     occurrences_tests!(weave::zdd2::Forest<&str>);
@@ -19,7 +23,7 @@ mod zdd_tests {
 
 #[cfg(test)]
 mod serde_tests {
-    use serde_test::{assert_tokens, Token};
+    use serde_test::{assert_de_tokens, assert_ser_tokens, assert_tokens, Configure, Token};
 
     use weave::zdd2::Forest;
 
@@ -27,12 +31,20 @@ mod serde_tests {
     fn empty() {
         let forest = Forest::<&str>::empty();
 
-        assert_tokens(&forest, &[
+        assert_tokens(&forest.readable(), &[
             Token::Struct { name: "Forest", len: 2 },
             Token::Str("root"),
 
             Token::NewtypeStruct { name: "Node" },
-            Token::Str("(N)"),
+            Token::Struct { name: "NodeTable", len: 3 },
+            Token::Str("version"),
+            Token::U64(2),
+            Token::Str("nodes"),
+            Token::Seq { len: Some(0) },
+            Token::SeqEnd,
+            Token::Str("root"),
+            Token::U64(0),
+            Token::StructEnd,
 
             Token::Str("universe"),
             Token::NewtypeStruct { name: "Universe" },
@@ -48,12 +60,32 @@ mod serde_tests {
     fn unit() {
         let forest = Forest::unit(&["1", "2"]);
 
-        assert_tokens(&forest, &[
+        assert_tokens(&forest.readable(), &[
             Token::Struct { name: "Forest", len: 2 },
             Token::Str("root"),
 
             Token::NewtypeStruct { name: "Node" },
-            Token::Str("(0 (N) (1 (N) (A)))"),
+            Token::Struct { name: "NodeTable", len: 3 },
+            Token::Str("version"),
+            Token::U64(2),
+            Token::Str("nodes"),
+            Token::Seq { len: Some(2) },
+            Token::Tuple { len: 4 },
+            Token::U64(2),
+            Token::U64(1),
+            Token::U64(0),
+            Token::U64(1),
+            Token::TupleEnd,
+            Token::Tuple { len: 4 },
+            Token::U64(3),
+            Token::U64(0),
+            Token::U64(0),
+            Token::U64(2),
+            Token::TupleEnd,
+            Token::SeqEnd,
+            Token::Str("root"),
+            Token::U64(3),
+            Token::StructEnd,
 
             Token::Str("universe"),
             Token::NewtypeStruct { name: "Universe" },
@@ -76,7 +108,119 @@ mod serde_tests {
             vec!["2", "3"]
         ]);
 
-        assert_tokens(&forest, &[
+        assert_tokens(&forest.readable(), &[
+            Token::Struct { name: "Forest", len: 2 },
+            Token::Str("root"),
+
+            Token::NewtypeStruct { name: "Node" },
+            Token::Struct { name: "NodeTable", len: 3 },
+            Token::Str("version"),
+            Token::U64(2),
+            Token::Str("nodes"),
+            Token::Seq { len: Some(3) },
+            Token::Tuple { len: 4 },
+            Token::U64(2),
+            Token::U64(2),
+            Token::U64(0),
+            Token::U64(1),
+            Token::TupleEnd,
+            Token::Tuple { len: 4 },
+            Token::U64(3),
+            Token::U64(1),
+            Token::U64(2),
+            Token::U64(1),
+            Token::TupleEnd,
+            Token::Tuple { len: 4 },
+            Token::U64(4),
+            Token::U64(0),
+            Token::U64(0),
+            Token::U64(3),
+            Token::TupleEnd,
+            Token::SeqEnd,
+            Token::Str("root"),
+            Token::U64(4),
+            Token::StructEnd,
+
+            Token::Str("universe"),
+            Token::NewtypeStruct { name: "Universe" },
+
+            Token::Map { len: Some(3) },
+            Token::BorrowedStr("1"),
+            Token::U64(1),
+            Token::BorrowedStr("2"),
+            Token::U64(2),
+            Token::BorrowedStr("3"),
+            Token::U64(1),
+            Token::MapEnd,
+
+            Token::StructEnd,
+        ]);
+    }
+
+    #[test]
+    fn sifted_forests_are_written_in_the_default_order() {
+        let forest = Forest::many(&[
+            vec!["1", "2"],
+            vec!["2", "3"]
+        ]);
+
+        assert_ser_tokens(&forest.sift(), &[
+            Token::Struct { name: "Forest", len: 2 },
+            Token::Str("root"),
+
+            Token::NewtypeStruct { name: "Node" },
+            Token::Struct { name: "NodeTable", len: 3 },
+            Token::Str("version"),
+            Token::U64(2),
+            Token::Str("nodes"),
+            Token::Seq { len: Some(3) },
+            Token::Tuple { len: 4 },
+            Token::U64(2),
+            Token::U64(2),
+            Token::U64(0),
+            Token::U64(1),
+            Token::TupleEnd,
+            Token::Tuple { len: 4 },
+            Token::U64(3),
+            Token::U64(1),
+            Token::U64(2),
+            Token::U64(1),
+            Token::TupleEnd,
+            Token::Tuple { len: 4 },
+            Token::U64(4),
+            Token::U64(0),
+            Token::U64(0),
+            Token::U64(3),
+            Token::TupleEnd,
+            Token::SeqEnd,
+            Token::Str("root"),
+            Token::U64(4),
+            Token::StructEnd,
+
+            Token::Str("universe"),
+            Token::NewtypeStruct { name: "Universe" },
+
+            Token::Map { len: Some(3) },
+            Token::BorrowedStr("1"),
+            Token::U64(1),
+            Token::BorrowedStr("2"),
+            Token::U64(2),
+            Token::BorrowedStr("3"),
+            Token::U64(1),
+            Token::MapEnd,
+
+            Token::StructEnd,
+        ]);
+    }
+
+    #[test]
+    fn nested_strings_are_still_read() {
+        let forest = Forest::many(&[
+            vec!["1", "2"],
+            vec!["2", "3"]
+        ]);
+
+        assert_de_tokens(&forest.readable(), &[
             Token::Struct { name: "Forest", len: 2 },
             Token::Str("root"),
 
@@ -98,4 +242,20 @@ mod serde_tests {
             Token::StructEnd,
         ]);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn shared_nodes_keep_the_output_small() {
+        let forest = (0..30)
+            .map(|group| Forest::many(&[vec![format!("{}-a", group)], vec![format!("{}-b", group)]]))
+            .fold(Forest::unit(&[String::from("root")]), Forest::product);
+
+        assert_eq!(1 << 30, forest.count());
+
+        let json = serde_json::to_string(&forest).unwrap();
+        let bytes = bincode::serialize(&forest).unwrap();
+
+        assert!(json.len() < 4_000, "{} bytes of json", json.len());
+        assert_eq!(forest, serde_json::from_str(&json).unwrap());
+        assert_eq!(forest, bincode::deserialize(&bytes).unwrap());
+    }
+}